@@ -0,0 +1,94 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use anyhow::Context;
+use common::{Args, Puzzle, DEFAULT_INPUT, LATEST_YEAR};
+
+/// Removes `<name> <value>` from the arguments and returns the value,
+/// so the remaining arguments can be handed to `Args::parse`.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> anyhow::Result<Option<String>> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+
+    let value = args
+        .get(index + 1)
+        .cloned()
+        .context(format!("missing value after {name}"))?;
+    args.drain(index..=index + 1);
+
+    Ok(Some(value))
+}
+
+/// `--day <day>` of `--year <year>`, the latest season if no year is given.
+pub fn take_puzzle(args: &mut Vec<String>) -> anyhow::Result<Puzzle> {
+    let year = take_parsed(args, "--year")?.unwrap_or(LATEST_YEAR);
+    let day = take_option(args, "--day")?.context("missing --day <day>")?;
+
+    Ok(Puzzle::new(year, parse_day(&day)?))
+}
+
+pub fn parse_day(day: &str) -> anyhow::Result<u8> {
+    day.parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .context(format!("invalid --day {day:?}, expected 1 to 25"))
+}
+
+/// Removes `<name> <value>` from the arguments and parses the value.
+pub fn take_parsed<T: FromStr>(args: &mut Vec<String>, name: &str) -> anyhow::Result<Option<T>> {
+    take_option(args, name)?
+        .map(|value| {
+            value
+                .parse()
+                .ok()
+                .context(format!("invalid {name} {value:?}"))
+        })
+        .transpose()
+}
+
+/// The input given with `--input`, otherwise the day's own `input.txt`.
+pub fn input_path(puzzle: Puzzle, args: &Args) -> PathBuf {
+    args.input
+        .clone()
+        .unwrap_or_else(|| puzzle.dir().join(DEFAULT_INPUT))
+}
+
+pub fn read_input(puzzle: Puzzle, args: &Args) -> anyhow::Result<String> {
+    let path = input_path(puzzle, args);
+
+    fs::read_to_string(&path).context(format!("failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_option() {
+        let mut values = args(&["--explain", "--day", "7", "--format", "json"]);
+
+        assert_eq!(take_puzzle(&mut values).unwrap(), Puzzle::new(2022, 7));
+        assert_eq!(
+            take_option(&mut values, "--format").unwrap(),
+            Some("json".to_string())
+        );
+        assert_eq!(take_option(&mut values, "--format").unwrap(), None);
+        assert_eq!(values, args(&["--explain"]));
+
+        assert!(take_puzzle(&mut args(&["--day", "26"])).is_err());
+        assert_eq!(
+            take_puzzle(&mut args(&["--day", "3", "--year", "2023"])).unwrap(),
+            Puzzle::new(2023, 3)
+        );
+        assert_eq!(
+            take_parsed::<usize>(&mut args(&["--steps", "4"]), "--steps").unwrap(),
+            Some(4)
+        );
+        assert!(take_parsed::<usize>(&mut args(&["--steps", "x"]), "--steps").is_err());
+        assert!(take_option(&mut args(&["--format"]), "--format").is_err());
+    }
+}
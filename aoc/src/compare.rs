@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+
+use common::{Answers, Args, Budget, Config, Context, Diagnostics, Explain, Implementation};
+
+use crate::{
+    cli,
+    external::ExternalSolver,
+    generate::Rng,
+    registry::{self, Day},
+};
+
+/// What one solver made of one input.
+pub struct Run {
+    pub solver: String,
+    pub result: anyhow::Result<(Answers, Duration)>,
+}
+
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let day = registry::day(cli::take_puzzle(&mut args)?)?;
+
+    let mut solvers = Vec::new();
+    while let Some(command) = cli::take_option(&mut args, "--solver")? {
+        solvers.push(ExternalSolver::parse(&command)?);
+    }
+
+    let generated = cli::take_parsed::<usize>(&mut args, "--generated")?;
+    let seed = cli::take_parsed(&mut args, "--seed")?.unwrap_or(2022);
+
+    let args = Args::parse(args)?;
+    let config = Config::load(day.puzzle(), &args)?;
+    let implementations = day.implementations(&args)?;
+
+    // a day with several implementations can be compared with itself
+    if solvers.is_empty() && implementations.len() < 2 {
+        anyhow::bail!("missing --solver <command>");
+    }
+
+    let inputs = match generated {
+        Some(count) => {
+            let mut rng = Rng::new(seed);
+            (1..=count)
+                .map(|i| {
+                    let input = (day.generate)(&mut rng, day.sample_size);
+                    (format!("generated input {i}"), input)
+                })
+                .collect()
+        }
+        None => vec![(
+            cli::input_path(day.puzzle(), &args).display().to_string(),
+            cli::read_input(day.puzzle(), &args)?,
+        )],
+    };
+
+    let mut mismatches = Vec::new();
+
+    for (name, input) in inputs {
+        let runs = compare(day, &config, &args, &implementations, &solvers, &input);
+
+        println!("{name}:");
+        print_runs(&runs);
+
+        mismatches.extend(
+            self::mismatches(&runs)
+                .into_iter()
+                .map(|mismatch| format!("{name}: {mismatch}")),
+        );
+    }
+
+    match mismatches.is_empty() {
+        true => Ok(()),
+        false => anyhow::bail!(
+            "{} mismatches:\n  {}",
+            mismatches.len(),
+            mismatches.join("\n  ")
+        ),
+    }
+}
+
+/// Runs our implementations, then every external solver, on the same input.
+/// The first of our implementations is the one the others are checked against.
+pub fn compare(
+    day: &Day,
+    config: &Config,
+    args: &Args,
+    implementations: &[Implementation],
+    solvers: &[ExternalSolver],
+    input: &str,
+) -> Vec<Run> {
+    let mut runs = implementations
+        .iter()
+        .map(|implementation| {
+            let context = Context {
+                config: config.clone(),
+                explain: Explain::disabled(),
+                budget: Budget::new(args.time_limit, args.memory_limit),
+                diagnostics: Diagnostics::strict(),
+            };
+
+            let started = Instant::now();
+            let result =
+                (implementation.solve)(&context, input).map(|answers| (answers, started.elapsed()));
+
+            let solver = match day.implementations.len() {
+                1 => "rust".to_string(),
+                _ => format!("rust ({})", implementation.name),
+            };
+
+            Run { solver, result }
+        })
+        .collect::<Vec<_>>();
+
+    runs.extend(solvers.iter().map(|solver| Run {
+        solver: solver.name.clone(),
+        result: solver.solve(input, args.time_limit),
+    }));
+
+    runs
+}
+
+/// Every external solver whose answers differ from ours, or that failed.
+pub fn mismatches(runs: &[Run]) -> Vec<String> {
+    let Some((reference, others)) = runs.split_first() else {
+        return Vec::new();
+    };
+
+    let expected = match &reference.result {
+        Ok((answers, _)) => answers,
+        Err(error) => return vec![format!("{} failed: {error:#}", reference.solver)],
+    };
+
+    others
+        .iter()
+        .filter_map(|run| match &run.result {
+            Ok((answers, _)) if answers == expected => None,
+            Ok((answers, _)) => Some(format!(
+                "{} answered {:?} and {:?} instead of {:?} and {:?}",
+                run.solver, answers.part1, answers.part2, expected.part1, expected.part2
+            )),
+            Err(error) => Some(format!("{error:#}")),
+        })
+        .collect()
+}
+
+fn print_runs(runs: &[Run]) {
+    let reference = runs
+        .first()
+        .and_then(|run| run.result.as_ref().ok())
+        .map(|(_, time)| *time);
+
+    for run in runs {
+        match &run.result {
+            Ok((answers, time)) => {
+                let relative = reference.map_or("-".to_string(), |reference| {
+                    format!("{:.2}x", time.as_secs_f64() / reference.as_secs_f64())
+                });
+
+                println!(
+                    "  {:<24}  {:>16}  {:>16}  {:>12}  {relative:>10}",
+                    run.solver,
+                    answers.part1,
+                    answers.part2,
+                    format!("{time:.2?}")
+                );
+            }
+            Err(error) => println!("  {:<24}  failed: {error:#}", run.solver),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use common::Puzzle;
+
+    use super::*;
+    use crate::external::tests::script;
+
+    const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n";
+
+    #[test]
+    fn test_compare() {
+        let right = script("right", "cat > /dev/null\necho 24000\necho 45000\n");
+        let wrong = script(
+            "wrong",
+            "cat > /dev/null\necho 'part 1: 24000'\necho 'part 2: 41000'\n",
+        );
+
+        let solvers = [&right, &wrong]
+            .map(|path| ExternalSolver::parse(&format!("sh {}", path.display())).unwrap());
+
+        let day = registry::day(Puzzle::new(2022, 1)).unwrap();
+        let runs = compare(
+            day,
+            &Config::default(),
+            &Args::default(),
+            day.implementations,
+            &solvers,
+            INPUT,
+        );
+
+        // both implementations of the day run before the external solvers
+        assert_eq!(runs.len(), 4);
+        for run in &runs[..2] {
+            assert_eq!(run.result.as_ref().unwrap().0, Answers::new(24000, 45000));
+        }
+
+        let mismatches = mismatches(&runs);
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0]
+            .ends_with(r#"answered "24000" and "41000" instead of "24000" and "45000""#));
+
+        fs::remove_file(right).unwrap();
+        fs::remove_file(wrong).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::BufReader;
+
+use common::{Args, Context, Puzzle};
+use serde_json::{json, Value};
+
+use crate::cli;
+
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let puzzle = cli::take_puzzle(&mut args)?;
+    let format = cli::take_option(&mut args, "--format")?.unwrap_or_else(|| "json".to_string());
+
+    if format != "json" {
+        anyhow::bail!("unknown format {format:?}, only json is supported");
+    }
+
+    let args = Args::parse(args)?;
+    let context = Context::new(puzzle, &args)?;
+    let input = cli::read_input(puzzle, &args)?;
+
+    let state = state(puzzle, &context, &input)?;
+
+    if let Some(summary) = context.diagnostics.summary(common::DEFAULT_EXPLAIN_LIMIT) {
+        eprintln!("{summary}");
+    }
+
+    println!("{}", serde_json::to_string_pretty(&state)?);
+
+    Ok(())
+}
+
+/// The state a day builds from its input, before any answer is computed from it.
+pub fn state(puzzle: Puzzle, context: &Context, input: &str) -> anyhow::Result<Value> {
+    let reader = BufReader::new(input.as_bytes());
+    let diagnostics = &context.diagnostics;
+
+    let state = match (puzzle.year, puzzle.day) {
+        (2022, 5) => {
+            use y2022_day05::crate_mover::{CrateMover, CrateMover9000};
+
+            serde_json::to_value(CrateMover::<CrateMover9000>::parse(reader, diagnostics)?)?
+        }
+        (2022, 7) => {
+            serde_json::to_value(y2022_day07::parser::Parser::parse(reader, diagnostics)?)?
+        }
+        (2022, 8) => {
+            serde_json::to_value(y2022_day08::parser::Parser::parse(reader, diagnostics)?)?
+        }
+        (2022, 9) => {
+            use y2022_day09::{parser::Parser, MIN_KNOTS};
+
+            // the rope is only interesting once the motions have been applied
+            let mut ropes = [
+                Parser::new(context.config.get_at_least(1, "knots", 2, MIN_KNOTS)?),
+                Parser::new(context.config.get_at_least(2, "knots", 10, MIN_KNOTS)?),
+            ];
+
+            for line in input.lines() {
+                for rope in &mut ropes {
+                    diagnostics.recover(rope.move_head(line))?;
+                }
+            }
+
+            let [part1, part2] = ropes;
+            json!({ "part1": part1, "part2": part2 })
+        }
+        _ => anyhow::bail!("{puzzle} has no state to dump"),
+    };
+
+    Ok(state)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state() {
+        let context = Context::default();
+
+        let dump = state(
+            Puzzle::new(2022, 7),
+            &context,
+            "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n12 b\n",
+        )
+        .unwrap();
+        assert_eq!(dump, json!({ "directory_sizes": { "/": 12, "/a": 12 } }));
+
+        let dump = state(Puzzle::new(2022, 8), &context, "123\n456\n").unwrap();
+        assert_eq!(
+            dump,
+            json!({ "rows": 2, "columns": 3, "trees": [1, 2, 3, 4, 5, 6] })
+        );
+
+        let dump = state(Puzzle::new(2022, 9), &context, "R 2\n").unwrap();
+        assert_eq!(
+            dump["part1"]["knots"],
+            json!([{ "x": 2, "y": 0 }, { "x": 1, "y": 0 }])
+        );
+
+        // the visited positions come sorted, however the set keeps them
+        let dump = state(Puzzle::new(2022, 9), &context, "R 3\nU 2\nL 4\n").unwrap();
+        assert_eq!(
+            dump["part1"]["visited"],
+            json!([
+                { "x": 0, "y": -2 },
+                { "x": 0, "y": 0 },
+                { "x": 1, "y": -2 },
+                { "x": 1, "y": 0 },
+                { "x": 2, "y": -2 },
+                { "x": 2, "y": 0 },
+                { "x": 3, "y": -1 }
+            ])
+        );
+
+        assert!(state(Puzzle::new(2022, 1), &context, "").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    io::{Read, Write},
+    process::{Command, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use common::Answers;
+
+/// How often a running solver is checked on while waiting for it.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A solver written in anything, run as a separate process.
+///
+/// The input is written to its stdin and the first two non-empty lines of its stdout are the answers
+/// to part 1 and part 2, optionally prefixed with `part 1:` or `part1:` as the days print them.
+#[derive(Debug, Clone)]
+pub struct ExternalSolver {
+    pub name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl ExternalSolver {
+    /// A command line such as `python3 day01.py`, split on whitespace.
+    pub fn parse(command: &str) -> anyhow::Result<Self> {
+        let mut words = command.split_whitespace().map(str::to_string);
+        let program = words.next().context("empty solver command")?;
+
+        Ok(Self {
+            name: command.trim().to_string(),
+            program,
+            args: words.collect(),
+        })
+    }
+
+    /// Runs the solver on the input, killing it once `time_limit` has passed.
+    pub fn solve(
+        &self,
+        input: &str,
+        time_limit: Option<Duration>,
+    ) -> anyhow::Result<(Answers, Duration)> {
+        let started = Instant::now();
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(format!("failed to start {}", self.name))?;
+
+        // stdin, stdout and stderr are each served from their own thread,
+        // so a solver filling one of them can't block on another
+        let mut stdin = child.stdin.take().context("no stdin")?;
+        let input = input.to_string();
+        let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let reader = read_all(child.stdout.take().context("no stdout")?);
+        let errors = read_all(child.stderr.take().context("no stderr")?);
+
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            if time_limit.is_some_and(|limit| started.elapsed() > limit) {
+                child.kill()?;
+                child.wait()?;
+                anyhow::bail!("{} took longer than {:?}", self.name, time_limit.unwrap());
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+        let elapsed = started.elapsed();
+
+        // a solver is allowed to exit without reading all of its input
+        let _ = writer.join();
+        let output = reader.join().expect("the stdout reader panicked")?;
+        let stderr = errors.join().expect("the stderr reader panicked")?;
+
+        if !status.success() {
+            anyhow::bail!("{} failed with {status}: {}", self.name, stderr.trim());
+        }
+
+        Ok((parse_answers(&output)?, elapsed))
+    }
+}
+
+/// Reads a pipe to its end on a thread of its own.
+fn read_all(mut pipe: impl Read + Send + 'static) -> JoinHandle<std::io::Result<String>> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        pipe.read_to_string(&mut output).map(|_| output)
+    })
+}
+
+pub fn parse_answers(output: &str) -> anyhow::Result<Answers> {
+    let mut lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    let mut answer = |part: &str| {
+        let line = lines
+            .next()
+            .context(format!("no answer to part {part} in the output"))?;
+
+        let answer = [format!("part {part}:"), format!("part{part}:")]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix.as_str()))
+            .unwrap_or(line);
+
+        anyhow::Ok(answer.trim().to_string())
+    };
+
+    Ok(Answers::new(answer("1")?, answer("2")?))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    /// Writes a shell script standing in for a solver written in another language.
+    pub fn script(name: &str, body: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("aoc-{}-{name}.sh", std::process::id()));
+        fs::write(&path, body).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("part 1: 24000\npart 2: 45000\n").unwrap(),
+            Answers::new(24000, 45000)
+        );
+        assert_eq!(
+            parse_answers("\nCMZ\npart2:MCD\n").unwrap(),
+            Answers::new("CMZ", "MCD")
+        );
+        assert!(parse_answers("24000\n").is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        // counts the lines it was given, which shows the input really went through stdin
+        let path = script("count", "wc -l | tr -d ' '\necho 'part 2: done'\n");
+        let solver = ExternalSolver::parse(&format!("sh {}", path.display())).unwrap();
+
+        let (answers, elapsed) = solver.solve("a\nb\nc\n", None).unwrap();
+        assert_eq!(answers, Answers::new(3, "done"));
+        assert!(!elapsed.is_zero());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_solve_failures() {
+        let path = script("fail", "echo 'no idea' >&2\nexit 3\n");
+        let solver = ExternalSolver::parse(&format!("sh {}", path.display())).unwrap();
+
+        let error = solver.solve("", None).unwrap_err().to_string();
+        assert!(error.ends_with("no idea"), "{error}");
+        fs::remove_file(path).unwrap();
+
+        let path = script("slow", "sleep 5\n");
+        let solver = ExternalSolver::parse(&format!("sh {}", path.display())).unwrap();
+
+        let error = solver
+            .solve("", Some(Duration::from_millis(50)))
+            .unwrap_err();
+        assert!(error.to_string().contains("took longer than"));
+        fs::remove_file(path).unwrap();
+
+        assert!(ExternalSolver::parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_solve_logging() {
+        // far more logging than a pipe buffer holds, which has to be read while the solver runs
+        let path = script(
+            "logging",
+            "cat > /dev/null
+head -c 300000 /dev/zero | tr '\\0' x >&2
+echo 24000
+echo 45000
+",
+        );
+        let solver = ExternalSolver::parse(&format!("sh {}", path.display())).unwrap();
+
+        let (answers, _) = solver
+            .solve("1000\n", Some(Duration::from_secs(10)))
+            .unwrap();
+        assert_eq!(answers, Answers::new(24000, 45000));
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt::Write;
+
+/// Makes up an input of the given size, in records of the day.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// SplitMix64, plenty for made-up inputs and the same on every machine.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`.
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_u64() % (high - low + 1) as u64) as usize
+    }
+
+    pub fn letter(&mut self, letters: &[u8]) -> char {
+        letters[self.range(0, letters.len() - 1)] as char
+    }
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// just under the disk of day 7
+const DISK_SPACE: usize = 69_000_000;
+
+/// `size` elves carrying one to five items each.
+pub fn calories(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for elf in 0..size {
+        if elf > 0 {
+            input.push('\n');
+        }
+
+        for _ in 0..rng.range(1, 5) {
+            writeln!(input, "{}", rng.range(1000, 70000)).unwrap();
+        }
+    }
+
+    input
+}
+
+/// `size` rounds.
+pub fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.letter(b"ABC"), rng.letter(b"XYZ")))
+        .collect()
+}
+
+/// `size` groups of three rucksacks.
+pub fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    (0..size * 3)
+        .map(|_| {
+            let length = rng.range(8, 24) * 2;
+            let mut rucksack = (0..length).map(|_| rng.letter(LETTERS)).collect::<String>();
+            rucksack.push('\n');
+            rucksack
+        })
+        .collect()
+}
+
+/// `size` pairs of section assignments.
+pub fn assignments(rng: &mut Rng, size: usize) -> String {
+    let assignment = |rng: &mut Rng| {
+        let start = rng.range(1, 99);
+        (start, rng.range(start, 99))
+    };
+
+    (0..size)
+        .map(|_| {
+            let (a, b) = assignment(rng);
+            let (c, d) = assignment(rng);
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+/// Nine stacks of eight crates and `size` moves, each taking crates that are really there.
+pub fn crate_moves(rng: &mut Rng, size: usize) -> String {
+    const STACKS: usize = 9;
+    const HEIGHT: usize = 8;
+
+    let mut input = String::new();
+
+    for _ in 0..HEIGHT {
+        let row = (0..STACKS)
+            .map(|_| format!("[{}]", rng.letter(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ")))
+            .collect::<Vec<_>>();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+
+    let labels = (1..=STACKS).map(|i| format!(" {i} ")).collect::<Vec<_>>();
+    writeln!(input, "{}\n", labels.join(" ")).unwrap();
+
+    let mut heights = [HEIGHT; STACKS];
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.range(0, STACKS - 1);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + rng.range(1, STACKS - 1)) % STACKS;
+        let amount = rng.range(1, heights[from].min(5));
+
+        heights[from] -= amount;
+        heights[to] += amount;
+
+        writeln!(input, "move {amount} from {} to {}", from + 1, to + 1).unwrap();
+    }
+
+    input
+}
+
+/// A datastream of `size` characters drawn from thirteen letters, so longer markers never show up
+/// and the whole stream has to be read.
+pub fn datastream(rng: &mut Rng, size: usize) -> String {
+    let mut stream = (0..size)
+        .map(|_| rng.letter(&LOWERCASE[..13]))
+        .collect::<String>();
+    stream.push('\n');
+    stream
+}
+
+/// A transcript visiting `size` directories, each holding a few files.
+///
+/// Files get smaller as there are more directories, so that they all fit on the 70000000 disk of the puzzle.
+pub fn transcript(rng: &mut Rng, size: usize) -> String {
+    let largest = (DISK_SPACE / (3 * size.max(1))).clamp(1, 300000);
+    let mut input = "$ cd /\n$ ls\n".to_string();
+    let mut depth = 0;
+
+    for directory in 0..size {
+        for _ in 0..rng.range(0, depth) {
+            input.push_str("$ cd ..\n");
+            depth -= 1;
+        }
+
+        writeln!(input, "$ cd d{directory}\n$ ls").unwrap();
+        depth += 1;
+
+        for file in 0..rng.range(1, 3) {
+            writeln!(input, "{} f{file}", rng.range(largest / 300 + 1, largest)).unwrap();
+        }
+    }
+
+    input
+}
+
+/// A square grid of `size` rows.
+pub fn grid(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| rng.letter(b"0123456789"))
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// `size` motions.
+pub fn motions(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.letter(b"UDLR"), rng.range(1, 20)))
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{fs, io::BufReader};
+
+    use common::{Budget, Context, Diagnostics};
+    use y2022_day01::{parallel, parser::Parser, statistics::Statistics};
+
+    use super::*;
+    use crate::{registry::DAYS, scaling::DEFAULT_STEPS};
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in DAYS {
+            let input = (day.generate)(&mut Rng::new(1), 30);
+
+            // every implementation of a day has to come up with the same answers
+            let mut expected = None;
+
+            for implementation in day.implementations {
+                let answers = match (implementation.solve)(&Context::default(), &input) {
+                    Ok(answers) => answers,
+                    Err(error) => panic!(
+                        "{} ({}) can't solve its generated input: {error:#}",
+                        day.puzzle(),
+                        implementation.name
+                    ),
+                };
+
+                assert_eq!(
+                    expected.get_or_insert_with(|| answers.clone()),
+                    &answers,
+                    "{} ({})",
+                    day.puzzle(),
+                    implementation.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_largest_inputs_solve() {
+        // the largest inputs `aoc scaling` makes by default have to be just as solvable
+        for day in DAYS {
+            let input = (day.generate)(&mut Rng::new(2022), day.sample_size << (DEFAULT_STEPS - 1));
+
+            for implementation in day.implementations {
+                if let Err(error) = (implementation.solve)(&Context::default(), &input) {
+                    panic!(
+                        "{} ({}) can't solve its largest generated input: {error:#}",
+                        day.puzzle(),
+                        implementation.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_calories() {
+        let path = std::env::temp_dir().join(format!("aoc-{}-calories.txt", std::process::id()));
+
+        // reading a generated file in chunks has to find the very same elves as reading it line by line
+        for (seed, size) in [(1, 1), (2, 7), (3, 500), (4, 20000)] {
+            fs::write(&path, calories(&mut Rng::new(seed), size)).unwrap();
+
+            let file = fs::File::open(&path).unwrap();
+            let expected = Parser::new(10)
+                .with_totals()
+                .with_statistics(Statistics::exact())
+                .read_from(BufReader::new(file), &Diagnostics::strict())
+                .unwrap();
+            let input = fs::read_to_string(&path).unwrap();
+
+            for threads in [1, 2, 5, 16] {
+                let parser = parallel::parse(
+                    &input,
+                    Parser::new(10)
+                        .with_totals()
+                        .with_statistics(Statistics::exact()),
+                    threads,
+                    &Budget::unlimited(),
+                    &Diagnostics::strict(),
+                )
+                .unwrap();
+
+                assert_eq!(
+                    parser.ranked_elves(),
+                    expected.ranked_elves(),
+                    "{size} elves on {threads} threads"
+                );
+                for calories in [0, 50000, 150000] {
+                    assert_eq!(
+                        parser.count_exceeding(calories),
+                        expected.count_exceeding(calories)
+                    );
+                }
+                assert_eq!(
+                    parser.statistics().unwrap().report(&[10.0, 90.0], 10),
+                    expected.statistics().unwrap().report(&[10.0, 90.0], 10),
+                    "statistics of {size} elves on {threads} threads"
+                );
+            }
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+
+        for _ in 0..100 {
+            let value = a.range(3, 5);
+            assert!((3..=5).contains(&value));
+            assert_eq!(value, b.range(3, 5));
+        }
+    }
+}
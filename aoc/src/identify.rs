@@ -0,0 +1,177 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use common::{Budget, Config, Context, Diagnostics, Explain};
+
+use crate::registry::{Day, DAYS};
+
+/// How long a single day's parsers may take on a file that probably isn't theirs.
+const TIME_LIMIT: Duration = Duration::from_secs(2);
+
+/// How much less likely a day becomes with every record its parsers skip.
+const SKIP_PENALTY: f64 = 0.1;
+
+pub struct Score {
+    pub day: &'static Day,
+    pub records: usize,
+    /// `None` if the parsers rejected the input outright
+    pub skipped: Option<usize>,
+}
+
+impl Score {
+    /// The share of records the day's parsers accepted, from 0 to 1.
+    pub fn parsed(&self) -> f64 {
+        match (self.skipped, self.records) {
+            (None, _) | (_, 0) => 0.0,
+            (Some(skipped), records) => (records - skipped) as f64 / records as f64,
+        }
+    }
+
+    fn likelihood(&self) -> f64 {
+        match (self.skipped, self.records) {
+            (Some(skipped), records) if skipped < records => SKIP_PENALTY.powi(skipped as i32),
+            _ => 0.0,
+        }
+    }
+}
+
+pub fn run(args: Vec<String>) -> anyhow::Result<()> {
+    let [path] = args.as_slice() else {
+        anyhow::bail!("usage: aoc identify <file>");
+    };
+
+    let path = PathBuf::from(path);
+    let input = fs::read_to_string(&path).context(format!("failed to read {}", path.display()))?;
+
+    let scores = score(&input);
+
+    println!("{:>10}  {:>7}  format", "day", "parsed");
+    for score in &scores {
+        println!(
+            "{:>10}  {:>6.1}%  {}",
+            score.day.puzzle().to_string(),
+            score.parsed() * 100.0,
+            score.day.format
+        );
+    }
+
+    println!();
+    println!("{}", verdict(&scores));
+
+    Ok(())
+}
+
+/// Scores every day against the input, the most likely day first.
+pub fn score(input: &str) -> Vec<Score> {
+    let records = input.lines().count();
+
+    let mut scores = DAYS
+        .iter()
+        .map(|day| {
+            // lenient parsing counts the bad records instead of stopping at the first one
+            let context = Context {
+                config: Config::default(),
+                explain: Explain::disabled(),
+                budget: Budget::new(Some(TIME_LIMIT), None),
+                diagnostics: Diagnostics::lenient(),
+            };
+
+            // failures the parsers can't skip, such as a missing stack drawing, rule the day out
+            let skipped = (day.validate)(&context, input)
+                .ok()
+                .map(|()| context.diagnostics.warnings().len().min(records));
+
+            Score {
+                day,
+                records,
+                skipped,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    scores.sort_by(|a, b| b.likelihood().total_cmp(&a.likelihood()));
+
+    scores
+}
+
+/// The most likely day, its confidence being its share of the likelihoods of all days.
+pub fn verdict(scores: &[Score]) -> String {
+    let total = scores.iter().map(Score::likelihood).sum::<f64>();
+
+    let Some(best) = scores.first().filter(|best| best.likelihood() > 0.0) else {
+        return "no day's parsers accept this file".to_string();
+    };
+
+    let confidence = best.likelihood() / total * 100.0;
+    let tied = scores
+        .iter()
+        .skip(1)
+        .filter(|score| score.likelihood() == best.likelihood())
+        .map(|score| score.day.puzzle().to_string())
+        .collect::<Vec<_>>();
+
+    match tied.is_empty() {
+        true => format!(
+            "likely {} ({}), confidence {confidence:.0}%",
+            best.day.puzzle(),
+            best.day.format
+        ),
+        false => format!(
+            "ambiguous: {} ({}) and {} parse it equally well, confidence {confidence:.0}%",
+            best.day.puzzle(),
+            best.day.format,
+            tied.join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn identify(input: &str) -> String {
+        verdict(&score(input))
+    }
+
+    #[test]
+    fn test_identify() {
+        assert_eq!(
+            identify("1000\n2000\n\n4000\n\n5000\n6000\n"),
+            "likely 2022 day 1 (calorie blocks), confidence 99%"
+        );
+        assert_eq!(
+            identify("A Y\nB X\nC Z\n"),
+            "likely 2022 day 2 (`A X` strategy guide), confidence 100%"
+        );
+        assert_eq!(
+            identify("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\n"),
+            "likely 2022 day 5 (stack drawing and moves), confidence 100%"
+        );
+        assert_eq!(
+            identify("$ cd /\n$ ls\n14848514 b.txt\n"),
+            "likely 2022 day 7 (`$ cd` terminal transcript), confidence 100%"
+        );
+        assert_eq!(
+            identify("R 4\nU 4\nL 3\n"),
+            "likely 2022 day 9 (`R 4` motions), confidence 100%"
+        );
+    }
+
+    #[test]
+    fn test_identify_partial_match() {
+        let scores = score("2-4,6-8\n2-3,4-5\n5-7,7-9\nnot a pair\n");
+
+        assert_eq!(scores[0].day.number, 4);
+        assert_eq!(scores[0].parsed(), 0.75);
+    }
+
+    #[test]
+    fn test_identify_ambiguous() {
+        // a small digit grid is also a single elf carrying a few items
+        assert_eq!(
+            identify("30373\n25512\n65332\n"),
+            "ambiguous: 2022 day 1 (calorie blocks) and 2022 day 8 parse it equally well, confidence 50%"
+        );
+        assert_eq!(identify("%%%\n"), "no day's parsers accept this file");
+    }
+}
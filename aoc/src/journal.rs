@@ -0,0 +1,73 @@
+use anyhow::Context;
+use common::{journal_path, Args, Feedback, Journal};
+
+use crate::cli;
+
+/// Records an answer tried by hand, or lists the attempts of a day when no answer is given.
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let puzzle = cli::take_puzzle(&mut args)?;
+    let part = cli::take_parsed::<u8>(&mut args, "--part")?;
+    let answer = cli::take_option(&mut args, "--answer")?;
+    let feedback = cli::take_option(&mut args, "--feedback")?
+        .map(|feedback| feedback.parse::<Feedback>())
+        .transpose()?;
+
+    let args = Args::parse(args)?;
+    let path = journal_path(&cli::input_path(puzzle, &args), args.journal.as_deref());
+    let mut journal = Journal::load(&path)?;
+
+    let Some(answer) = answer else {
+        print!("{}", listing(&journal));
+        return Ok(());
+    };
+
+    let part = part
+        .filter(|part| [1, 2].contains(part))
+        .context("missing --part 1|2")?;
+
+    let Some(feedback) = feedback else {
+        // without feedback this only asks whether the answer is worth submitting
+        match journal.check(part, &answer) {
+            Some(warning) => println!("warning: {warning}"),
+            None => println!("part {part} answer {answer} isn't known to be wrong"),
+        }
+        return Ok(());
+    };
+
+    if let Some(warning) = journal.check(part, &answer) {
+        eprintln!("warning: {warning}");
+    }
+
+    journal.record(part, &answer, feedback);
+    journal.save(&path)
+}
+
+fn listing(journal: &Journal) -> String {
+    journal
+        .attempts
+        .iter()
+        .map(|attempt| {
+            format!(
+                "part {}: {} ({})\n",
+                attempt.part, attempt.answer, attempt.feedback
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing() {
+        let mut journal = Journal::default();
+        journal.record(1, "70000", Feedback::TooHigh);
+        journal.record(1, "69177", Feedback::Correct);
+
+        assert_eq!(
+            listing(&journal),
+            "part 1: 70000 (too high)\npart 1: 69177 (correct)\n"
+        );
+    }
+}
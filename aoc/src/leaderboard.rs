@@ -0,0 +1,469 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::cli;
+
+/// The JSON export of a private leaderboard, only the parts used here.
+#[derive(Deserialize)]
+struct Export {
+    event: String,
+    members: BTreeMap<String, ExportMember>,
+}
+
+#[derive(Deserialize)]
+struct ExportMember {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    completion_day_level: BTreeMap<String, BTreeMap<String, ExportStar>>,
+}
+
+#[derive(Deserialize)]
+struct ExportStar {
+    get_star_ts: i64,
+}
+
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    /// seconds from the puzzle unlocking to each star, by day
+    pub stars: BTreeMap<u8, [Option<i64>; 2]>,
+}
+
+pub struct Leaderboard {
+    pub year: i64,
+    /// ordered by id, so ties are broken the same way on every run
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        let export = serde_json::from_str::<Export>(json)?;
+        let year = export
+            .event
+            .parse()
+            .context(format!("invalid event {:?}", export.event))?;
+
+        let mut members = export
+            .members
+            .into_values()
+            .map(|member| {
+                let mut stars = BTreeMap::new();
+
+                for (day, parts) in member.completion_day_level {
+                    let day = cli::parse_day(&day)?;
+                    let mut times = [None, None];
+
+                    for (part, star) in parts {
+                        let index = match part.as_str() {
+                            "1" => 0,
+                            "2" => 1,
+                            _ => anyhow::bail!("invalid part {part:?} on day {day}"),
+                        };
+                        times[index] = Some(star.get_star_ts - unlock(year, day));
+                    }
+
+                    stars.insert(day, times);
+                }
+
+                // the site shows members without a name this way too
+                let name = member
+                    .name
+                    .unwrap_or_else(|| format!("(anonymous user #{})", member.id));
+
+                Ok(Member {
+                    id: member.id,
+                    name,
+                    stars,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        members.sort_by_key(|member| member.id);
+
+        Ok(Self { year, members })
+    }
+
+    /// The days anyone got a star on.
+    pub fn days(&self) -> Vec<u8> {
+        let mut days = self
+            .members
+            .iter()
+            .flat_map(|member| member.stars.keys().copied())
+            .collect::<Vec<_>>();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    fn star(&self, member: usize, day: u8, part: usize) -> Option<i64> {
+        self.members[member]
+            .stars
+            .get(&day)
+            .and_then(|times| times[part])
+    }
+
+    /// The local score of every member after each day, scored like the site does: the first member to
+    /// get a star gets as many points as there are members, the second one point less and so on.
+    pub fn scores(&self) -> Vec<(u8, Vec<u64>)> {
+        let mut scores = vec![0; self.members.len()];
+
+        self.days()
+            .into_iter()
+            .map(|day| {
+                for part in 0..2 {
+                    let mut finishers = (0..self.members.len())
+                        .filter_map(|member| Some((self.star(member, day, part)?, member)))
+                        .collect::<Vec<_>>();
+                    finishers.sort();
+
+                    for (position, (_, member)) in finishers.into_iter().enumerate() {
+                        scores[member] += (self.members.len() - position) as u64;
+                    }
+                }
+
+                (day, scores.clone())
+            })
+            .collect()
+    }
+}
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+pub fn unlock(year: i64, day: u8) -> i64 {
+    (days_from_civil(year, 12, day as i64) * 24 + 5) * 3600
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = match month <= 2 {
+        true => year - 1,
+        false => year,
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Ranks with ties sharing a rank, 1 being the highest score.
+pub fn ranks(scores: &[u64]) -> Vec<usize> {
+    scores
+        .iter()
+        .map(|score| 1 + scores.iter().filter(|other| *other > score).count())
+        .collect()
+}
+
+pub fn median(values: &mut [i64]) -> Option<i64> {
+    values.sort();
+
+    match values.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(values[n / 2]),
+        n => Some((values[n / 2 - 1] + values[n / 2]) / 2),
+    }
+}
+
+fn delta(times: [Option<i64>; 2]) -> Option<i64> {
+    Some(times[1]? - times[0]?)
+}
+
+/// `HH:MM:SS`, with the hours going past a day for stars collected late.
+fn duration(seconds: Option<i64>) -> String {
+    match seconds {
+        Some(seconds) => format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        None => "-".to_string(),
+    }
+}
+
+fn seconds(seconds: Option<i64>) -> String {
+    seconds.map_or(String::new(), |seconds| seconds.to_string())
+}
+
+/// The name as a CSV field.
+fn quoted(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub fn times(leaderboard: &Leaderboard, csv: bool) -> String {
+    let mut report = match csv {
+        true => "day,member,part1_seconds,part2_seconds,delta_seconds\n".to_string(),
+        false => format!(
+            "{:>3}  {:<24}  {:>9}  {:>9}  {:>9}\n",
+            "day", "member", "part 1", "part 2", "delta"
+        ),
+    };
+
+    for day in leaderboard.days() {
+        for member in &leaderboard.members {
+            let Some(&times) = member.stars.get(&day) else {
+                continue;
+            };
+
+            report.push_str(&match csv {
+                true => format!(
+                    "{day},{},{},{},{}\n",
+                    quoted(&member.name),
+                    seconds(times[0]),
+                    seconds(times[1]),
+                    seconds(delta(times))
+                ),
+                false => format!(
+                    "{day:>3}  {:<24}  {:>9}  {:>9}  {:>9}\n",
+                    member.name,
+                    duration(times[0]),
+                    duration(times[1]),
+                    duration(delta(times))
+                ),
+            });
+        }
+    }
+
+    report
+}
+
+pub fn ranking(leaderboard: &Leaderboard, csv: bool) -> String {
+    let scores = leaderboard.scores();
+
+    if csv {
+        let mut report = "day,member,score,rank\n".to_string();
+
+        for (day, scores) in &scores {
+            for ((member, score), rank) in leaderboard.members.iter().zip(scores).zip(ranks(scores))
+            {
+                report.push_str(&format!("{day},{},{score},{rank}\n", quoted(&member.name)));
+            }
+        }
+
+        return report;
+    }
+
+    // one column with the rank after each day, the members in their final order
+    let last = scores.last().map(|(_, scores)| scores.clone());
+    let last = last.unwrap_or_else(|| vec![0; leaderboard.members.len()]);
+
+    let mut order = (0..leaderboard.members.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&member| std::cmp::Reverse(last[member]));
+
+    let mut report = format!("{:<24}  {:>5}", "member", "score");
+    for (day, _) in &scores {
+        report.push_str(&format!("  {day:>3}"));
+    }
+    report.push('\n');
+
+    let ranks = scores
+        .iter()
+        .map(|(_, scores)| ranks(scores))
+        .collect::<Vec<_>>();
+
+    for member in order {
+        report.push_str(&format!(
+            "{:<24}  {:>5}",
+            leaderboard.members[member].name, last[member]
+        ));
+        for ranks in &ranks {
+            report.push_str(&format!("  {:>3}", ranks[member]));
+        }
+        report.push('\n');
+    }
+
+    report
+}
+
+pub fn medians(leaderboard: &Leaderboard, csv: bool) -> String {
+    let mut report = match csv {
+        true => {
+            "day,part1_stars,part1_seconds,part2_stars,part2_seconds,delta_seconds\n".to_string()
+        }
+        false => format!(
+            "{:>3}  {:>5}  {:>9}  {:>5}  {:>9}  {:>9}\n",
+            "day", "stars", "part 1", "stars", "part 2", "delta"
+        ),
+    };
+
+    for day in leaderboard.days() {
+        let times = leaderboard
+            .members
+            .iter()
+            .filter_map(|member| member.stars.get(&day))
+            .collect::<Vec<_>>();
+
+        let mut part1 = times
+            .iter()
+            .filter_map(|times| times[0])
+            .collect::<Vec<_>>();
+        let mut part2 = times
+            .iter()
+            .filter_map(|times| times[1])
+            .collect::<Vec<_>>();
+        let mut deltas = times
+            .iter()
+            .filter_map(|&&times| delta(times))
+            .collect::<Vec<_>>();
+
+        let (stars1, stars2) = (part1.len(), part2.len());
+        let (part1, part2, deltas) = (median(&mut part1), median(&mut part2), median(&mut deltas));
+
+        report.push_str(&match csv {
+            true => format!(
+                "{day},{stars1},{},{stars2},{},{}\n",
+                seconds(part1),
+                seconds(part2),
+                seconds(deltas)
+            ),
+            false => format!(
+                "{day:>3}  {stars1:>5}  {:>9}  {stars2:>5}  {:>9}  {:>9}\n",
+                duration(part1),
+                duration(part2),
+                duration(deltas)
+            ),
+        });
+    }
+
+    report
+}
+
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let report = cli::take_option(&mut args, "--report")?;
+    let reports = match report.as_deref() {
+        None => vec!["times", "ranking", "medians"],
+        Some(report @ ("times" | "ranking" | "medians")) => vec![report],
+        Some(report) => {
+            anyhow::bail!("unknown report {report:?}, expected times, ranking or medians")
+        }
+    };
+    let csv = match cli::take_option(&mut args, "--format")?.as_deref() {
+        None | Some("table") => false,
+        Some("csv") => true,
+        Some(format) => anyhow::bail!("unknown format {format:?}, expected table or csv"),
+    };
+
+    let [path] = args.as_slice() else {
+        anyhow::bail!("usage: aoc leaderboard <file.json> [--report times|ranking|medians] [--format table|csv]");
+    };
+
+    let path = PathBuf::from(path);
+    let json = fs::read_to_string(&path).context(format!("failed to read {}", path.display()))?;
+    let leaderboard =
+        Leaderboard::parse(&json).context(format!("failed to parse {}", path.display()))?;
+
+    let reports = reports
+        .into_iter()
+        .map(|report| match report {
+            "times" => times(&leaderboard, csv),
+            "ranking" => ranking(&leaderboard, csv),
+            _ => medians(&leaderboard, csv),
+        })
+        .collect::<Vec<_>>();
+
+    if !csv {
+        println!(
+            "{}, {} members\n",
+            leaderboard.year,
+            leaderboard.members.len()
+        );
+    }
+
+    print!("{}", reports.join("\n"));
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // 2022-12-01 05:00 UTC and 2022-12-02 05:00 UTC
+    const DAY1: i64 = 1669870800;
+    const DAY2: i64 = DAY1 + 86400;
+
+    fn export() -> String {
+        format!(
+            r#"{{
+  "event": "2022",
+  "owner_id": 1,
+  "members": {{
+    "1": {{ "id": 1, "name": "Ada", "stars": 4, "local_score": 0, "completion_day_level": {{
+      "1": {{ "1": {{ "get_star_ts": {}, "star_index": 0 }}, "2": {{ "get_star_ts": {}, "star_index": 1 }} }},
+      "2": {{ "1": {{ "get_star_ts": {}, "star_index": 2 }}, "2": {{ "get_star_ts": {}, "star_index": 3 }} }}
+    }} }},
+    "2": {{ "id": 2, "name": null, "stars": 3, "local_score": 0, "completion_day_level": {{
+      "1": {{ "1": {{ "get_star_ts": {}, "star_index": 0 }}, "2": {{ "get_star_ts": {}, "star_index": 1 }} }},
+      "2": {{ "1": {{ "get_star_ts": {}, "star_index": 2 }} }}
+    }} }},
+    "3": {{ "id": 3, "name": "Grace", "stars": 0, "local_score": 0, "completion_day_level": {{}} }}
+  }}
+}}"#,
+            DAY1 + 600,
+            DAY1 + 900,
+            DAY2 + 3600,
+            DAY2 + 90000,
+            DAY1 + 300,
+            DAY1 + 1500,
+            DAY2 + 1800,
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(unlock(2022, 1), DAY1);
+
+        let leaderboard = Leaderboard::parse(&export()).unwrap();
+        assert_eq!(leaderboard.year, 2022);
+        assert_eq!(leaderboard.days(), vec![1, 2]);
+
+        let anonymous = &leaderboard.members[1];
+        assert_eq!(anonymous.name, "(anonymous user #2)");
+        assert_eq!(anonymous.stars[&2], [Some(1800), None]);
+    }
+
+    #[test]
+    fn test_scores() {
+        let leaderboard = Leaderboard::parse(&export()).unwrap();
+
+        // day 1: the anonymous user is first to part 1, Ada first to part 2
+        // day 2: the anonymous user is first to part 1 again, and alone on part 2
+        assert_eq!(
+            leaderboard.scores(),
+            vec![(1, vec![5, 5, 0]), (2, vec![10, 8, 0])]
+        );
+        assert_eq!(ranks(&[5, 5, 0]), vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn test_reports() {
+        let leaderboard = Leaderboard::parse(&export()).unwrap();
+
+        assert_eq!(
+            times(&leaderboard, true),
+            "day,member,part1_seconds,part2_seconds,delta_seconds
+1,\"Ada\",600,900,300
+1,\"(anonymous user #2)\",300,1500,1200
+2,\"Ada\",3600,90000,86400
+2,\"(anonymous user #2)\",1800,,
+"
+        );
+
+        assert_eq!(
+            medians(&leaderboard, false),
+            "day  stars     part 1  stars     part 2      delta
+  1      2   00:07:30      2   00:20:00   00:12:30
+  2      2   00:45:00      1   25:00:00   24:00:00
+"
+        );
+
+        let ranking = ranking(&leaderboard, false);
+        let lines = ranking.lines().collect::<Vec<_>>();
+        assert!(lines[1].starts_with("Ada"), "{ranking}");
+        assert!(lines[1].ends_with("   10    1    1"), "{ranking}");
+        assert!(lines[3].ends_with("    0    3    3"), "{ranking}");
+    }
+}
@@ -0,0 +1,59 @@
+mod cli;
+mod compare;
+mod dump;
+mod external;
+mod generate;
+mod identify;
+mod journal;
+mod leaderboard;
+mod minimize;
+mod partition;
+mod registry;
+mod scaling;
+mod statistics;
+
+use anyhow::Context;
+
+common::track_allocations!();
+
+const USAGE: &str = "usage: aoc <command> [options]
+
+commands:
+  compare --day <day> [--solver <command>...] [--generated <inputs>]
+                                      checks external solvers against ours
+  dump --day <day> [--format json]    writes the parsed state of a day
+  identify <file>                     guesses which day an input belongs to
+  journal --day <day> [--part <part> --answer <answer> [--feedback <feedback>]]
+                                      records or checks a submitted answer
+  leaderboard <file.json> [--report times|ranking|medians] [--format table|csv]
+                                      reports on a private leaderboard export
+  minimize --day <day> [--output <fixture>] [--message <text>]
+                                      shrinks an input the day fails on
+  partition --day 1 --teams <k> [--exact-limit <elves>]
+                                      splits the elves into teams carrying about as much
+  scaling [--day <day>] [--steps <n>] [--format table|csv] [--max-exponent <x>]
+                                      times days on generated inputs of growing size
+  statistics --day 1 [--percentiles <p,...>] [--bins <n>] [--sketch <accuracy>]
+                                      summarizes the calories the elves carry
+
+--year <year> picks the season of --day, the latest one by default";
+
+// tools working across days, run from the workspace root with `cargo run -p aoc -- <command>`
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().context(USAGE)?;
+    let args = args.collect::<Vec<_>>();
+
+    match command.as_str() {
+        "compare" => compare::run(args),
+        "dump" => dump::run(args),
+        "identify" => identify::run(args),
+        "journal" => journal::run(args),
+        "leaderboard" => leaderboard::run(args),
+        "minimize" => minimize::run(args),
+        "partition" => partition::run(args),
+        "scaling" => scaling::run(args),
+        "statistics" => statistics::run(args),
+        _ => anyhow::bail!("unknown command {command:?}\n\n{USAGE}"),
+    }
+}
@@ -0,0 +1,568 @@
+use std::{
+    collections::HashSet,
+    fs,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+};
+
+use anyhow::Context as _;
+use common::{Answers, Args, Budget, Config, Context, Diagnostics, Explain};
+
+use crate::{
+    cli,
+    registry::{self, Day},
+};
+
+/// Shrinks the lines of an input while `fails` holds, along the structure of the day's input.
+pub type Minimizer =
+    fn(&Config, Vec<String>, &mut dyn FnMut(&[String]) -> bool) -> anyhow::Result<Vec<String>>;
+
+/// What running a solver on an input led to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answers(Answers),
+    Error(String),
+    Panic(String),
+}
+
+/// The failure a minimized input has to keep on showing.
+pub struct Failure {
+    panics: bool,
+    /// only failures whose message contains this count, otherwise those with the same message as the
+    /// original one but for its numbers
+    message: Option<String>,
+    shape: String,
+}
+
+impl Failure {
+    /// The failure behind an outcome, `None` if the solver succeeded.
+    pub fn of(outcome: &Outcome, message: Option<String>) -> Option<Self> {
+        match outcome {
+            Outcome::Answers(_) => None,
+            Outcome::Error(original) => Some(Self {
+                panics: false,
+                message,
+                shape: shape(original),
+            }),
+            Outcome::Panic(original) => Some(Self {
+                panics: true,
+                message,
+                shape: shape(original),
+            }),
+        }
+    }
+
+    pub fn matches(&self, outcome: &Outcome) -> bool {
+        let message = match outcome {
+            Outcome::Answers(_) => return false,
+            Outcome::Error(message) => (!self.panics).then_some(message),
+            Outcome::Panic(message) => self.panics.then_some(message),
+        };
+
+        message.is_some_and(|message| match &self.message {
+            Some(expected) => message.contains(expected),
+            None => shape(message) == self.shape,
+        })
+    }
+}
+
+/// A message without its numbers, which are lines, columns and indexes bound to change as the input shrinks.
+fn shape(message: &str) -> String {
+    message
+        .split(|c: char| c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("#")
+}
+
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let day = registry::day(cli::take_puzzle(&mut args)?)?;
+    let output = cli::take_option(&mut args, "--output")?.map(PathBuf::from);
+    let message = cli::take_option(&mut args, "--message")?;
+
+    let args = Args::parse(args)?;
+    let config = Config::load(day.puzzle(), &args)?;
+    let input = cli::read_input(day.puzzle(), &args)?;
+
+    // an unknown implementation would look like a failure worth minimizing
+    common::select(day.implementations, args.implementation.as_deref())?;
+
+    // the solver is expected to panic over and over, once is enough to see the message
+    let quiet = QuietPanics::new();
+
+    let outcome = run_solver(day, &config, &args, &input);
+    let Some(failure) = Failure::of(&outcome, message) else {
+        anyhow::bail!(
+            "{} doesn't fail on this input, there's nothing to minimize",
+            day.puzzle()
+        );
+    };
+
+    if !failure.matches(&outcome) {
+        anyhow::bail!("{} fails differently: {outcome:?}", day.puzzle());
+    }
+
+    let minimized = minimize(day, &config, &input, |candidate| {
+        failure.matches(&run_solver(day, &config, &args, candidate))
+    })?;
+
+    drop(quiet);
+
+    let output =
+        output.unwrap_or_else(|| day.puzzle().dir().join("fixtures").join("minimized.txt"));
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&output, &minimized).context(format!("failed to write {}", output.display()))?;
+
+    println!(
+        "minimized {} lines to {}, still failing with {:?}",
+        input.lines().count(),
+        minimized.lines().count(),
+        run_solver(day, &config, &args, &minimized)
+    );
+    println!("written to {}", output.display());
+
+    Ok(())
+}
+
+/// Silences panic messages, putting the previous hook back when dropped.
+struct QuietPanics(Option<PanicHook>);
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+
+impl QuietPanics {
+    fn new() -> Self {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        Self(Some(hook))
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        if let Some(hook) = self.0.take() {
+            panic::set_hook(hook);
+        }
+    }
+}
+
+/// Runs a day on an input with a fresh budget, catching panics.
+pub fn run_solver(day: &Day, config: &Config, args: &Args, input: &str) -> Outcome {
+    let context = Context {
+        config: config.clone(),
+        explain: Explain::disabled(),
+        budget: Budget::new(args.time_limit, args.memory_limit),
+        diagnostics: Diagnostics::strict(),
+    };
+
+    let solve = match common::select(day.implementations, args.implementation.as_deref()) {
+        Ok(implementation) => implementation.solve,
+        Err(error) => return Outcome::Error(format!("{error:#}")),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(&context, input))) {
+        Ok(Ok(answers)) => Outcome::Answers(answers),
+        Ok(Err(error)) => Outcome::Error(format!("{error:#}")),
+        Err(payload) => Outcome::Panic(
+            payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Shrinks the input while `fails` holds, only removing parts that leave it well-formed for the day.
+pub fn minimize(
+    day: &Day,
+    config: &Config,
+    input: &str,
+    mut fails: impl FnMut(&str) -> bool,
+) -> anyhow::Result<String> {
+    let lines = input.lines().map(str::to_string).collect::<Vec<_>>();
+    let mut fails = |lines: &[String]| fails(&join(lines));
+
+    let lines = (day.minimize)(config, lines, &mut fails)?;
+
+    Ok(join(&lines))
+}
+
+/// Any line can go.
+pub fn lines(
+    _: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    Ok(ddmin(lines, each, fails))
+}
+
+pub fn calories(
+    _: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    Ok(ddmin(lines, elf_blocks, fails))
+}
+
+pub fn rucksacks(
+    config: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    let group_size = config.get_at_least(2, "group_size", 3, 1)?;
+
+    Ok(ddmin(
+        lines,
+        |lines| rucksack_groups(lines, group_size),
+        fails,
+    ))
+}
+
+pub fn crate_moves(
+    _: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    Ok(ddmin(lines, moves, fails))
+}
+
+pub fn transcript(
+    _: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    Ok(ddmin(lines, directories, fails))
+}
+
+fn join(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Delta debugging: removes ever smaller chunks of units while the input still fails.
+///
+/// A unit is a set of item indexes that can go without breaking the input's structure.
+/// Units are worked out again after every removal, as the indexes have moved.
+fn ddmin<T: Clone>(
+    mut items: Vec<T>,
+    units: impl Fn(&[T]) -> Vec<Vec<usize>>,
+    fails: &mut dyn FnMut(&[T]) -> bool,
+) -> Vec<T> {
+    let mut chunks = 2;
+
+    loop {
+        let current = units(&items);
+        if current.is_empty() {
+            return items;
+        }
+
+        let chunks_count = chunks.min(current.len());
+        let chunk_size = current.len().div_ceil(chunks_count);
+
+        let reduced = current.chunks(chunk_size).find_map(|chunk| {
+            let removed = chunk.iter().flatten().collect::<HashSet<_>>();
+            let candidate = items
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !removed.contains(i))
+                .map(|(_, item)| item.clone())
+                .collect::<Vec<_>>();
+
+            // an empty input tells nothing about a failure
+            (!candidate.is_empty() && candidate.len() < items.len() && fails(&candidate))
+                .then_some(candidate)
+        });
+
+        match reduced {
+            Some(candidate) => {
+                items = candidate;
+                chunks = chunks_count.saturating_sub(1).max(2);
+            }
+            None if chunks_count >= current.len() => return items,
+            None => chunks = (chunks_count * 2).min(current.len()),
+        }
+    }
+}
+
+fn each<T>(items: &[T]) -> Vec<Vec<usize>> {
+    (0..items.len()).map(|i| vec![i]).collect()
+}
+
+/// Whole elves with the blank lines after them, the last one with those before it, then single items
+/// but the first of each elf, so that no elf is left empty and no blank lines pile up.
+fn elf_blocks(lines: &[String]) -> Vec<Vec<usize>> {
+    let mut elves: Vec<Range<usize>> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        match (line.is_empty(), elves.last_mut()) {
+            (true, _) => {}
+            (false, Some(elf)) if elf.end == i => elf.end += 1,
+            (false, _) => elves.push(i..i + 1),
+        }
+    }
+
+    let blocks = elves
+        .iter()
+        .enumerate()
+        .map(|(k, elf)| match elves.get(k + 1) {
+            Some(next) => (elf.start..next.start).collect(),
+            None => match k.checked_sub(1) {
+                Some(previous) => (elves[previous].end..elf.end).collect(),
+                None => elf.clone().collect(),
+            },
+        });
+    let items = elves
+        .iter()
+        .flat_map(|elf| elf.clone().skip(1).map(|i| vec![i]));
+
+    blocks.chain(items).collect()
+}
+
+/// Whole groups, so the rucksacks after a removed one still form the same groups.
+fn rucksack_groups(lines: &[String], group_size: usize) -> Vec<Vec<usize>> {
+    (0..lines.len())
+        .collect::<Vec<_>>()
+        .chunks(group_size.max(1))
+        .map(|group| group.to_vec())
+        .collect()
+}
+
+/// The moves after the drawing, the drawing itself is left intact.
+fn moves(lines: &[String]) -> Vec<Vec<usize>> {
+    let Some(blank) = lines.iter().position(|line| line.is_empty()) else {
+        return Vec::new();
+    };
+
+    (blank + 1..lines.len()).map(|i| vec![i]).collect()
+}
+
+/// Every directory visited with `$ cd <dir>` up to its `$ cd ..`, then single lines other than `cd`.
+fn directories(lines: &[String]) -> Vec<Vec<usize>> {
+    let target = |line: &str| line.strip_prefix("$ cd ").map(str::to_string);
+
+    let mut units = Vec::new();
+
+    for (start, line) in lines.iter().enumerate() {
+        match target(line).as_deref() {
+            Some("/") | Some("..") => continue,
+            Some(_) => {}
+            None => {
+                units.push(vec![start]);
+                continue;
+            }
+        }
+
+        let mut depth = 1;
+        let mut end = lines.len() - 1;
+
+        for (i, line) in lines.iter().enumerate().skip(start + 1) {
+            match target(line).as_deref() {
+                Some("..") => depth -= 1,
+                // going back to the root closes every directory opened so far
+                Some("/") => {
+                    end = i - 1;
+                    break;
+                }
+                Some(_) => depth += 1,
+                None => {}
+            }
+
+            if depth == 0 {
+                end = i;
+                break;
+            }
+        }
+
+        units.push((start..=end).collect());
+    }
+
+    units
+}
+
+/// Rows and columns in turn, so the grid stays rectangular.
+pub fn grid(
+    _: &Config,
+    mut rows: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    loop {
+        let size = (rows.len(), rows.first().map_or(0, String::len));
+
+        rows = ddmin(rows, each, fails);
+
+        let columns = ddmin(transpose(&rows), each, &mut |columns: &[String]| {
+            fails(&transpose(columns))
+        });
+        rows = transpose(&columns);
+
+        if (rows.len(), rows.first().map_or(0, String::len)) == size {
+            return Ok(rows);
+        }
+    }
+}
+
+fn transpose(rows: &[String]) -> Vec<String> {
+    let rows = rows
+        .iter()
+        .map(|row| row.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    (0..width)
+        .map(|column| rows.iter().filter_map(|row| row.get(column)).collect())
+        .collect()
+}
+
+/// Whole lines, then the characters of each line.
+pub fn datastream(
+    _: &Config,
+    lines: Vec<String>,
+    fails: &mut dyn FnMut(&[String]) -> bool,
+) -> anyhow::Result<Vec<String>> {
+    let mut lines = ddmin(lines, each, fails);
+
+    for i in 0..lines.len() {
+        let characters = lines[i].chars().collect::<Vec<_>>();
+
+        let characters = ddmin(characters, each, &mut |characters: &[char]| {
+            let mut candidate = lines.clone();
+            candidate[i] = characters.iter().collect();
+            fails(&candidate)
+        });
+
+        lines[i] = characters.into_iter().collect();
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use common::Puzzle;
+
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_ddmin() {
+        let items = (0..100).collect::<Vec<_>>();
+
+        let minimized = ddmin(items, each, &mut |items: &[i32]| {
+            items.contains(&17) && items.contains(&60)
+        });
+
+        assert_eq!(minimized, vec![17, 60]);
+    }
+
+    #[test]
+    fn test_directories() {
+        let transcript =
+            lines("$ cd /\n$ ls\ndir a\n$ cd a\n$ cd b\n1 f\n$ cd ..\n$ cd ..\n$ cd c\n");
+
+        assert_eq!(
+            directories(&transcript),
+            vec![
+                vec![1],
+                vec![2],
+                vec![3, 4, 5, 6, 7],
+                vec![4, 5, 6],
+                vec![5],
+                vec![8]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minimize_moves() {
+        let day = registry::day(Puzzle::new(2022, 5)).unwrap();
+        let config = Config::default();
+
+        // the last move takes more crates than there are on stack 1
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 5 from 3 to 1\n";
+        let args = Args::default();
+        let failure = Failure::of(&run_solver(day, &config, &args, input), None).unwrap();
+
+        let minimized = minimize(day, &config, input, |candidate| {
+            failure.matches(&run_solver(day, &config, &args, candidate))
+        })
+        .unwrap();
+
+        assert_eq!(
+            minimized,
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 5 from 3 to 1\n"
+        );
+    }
+
+    #[test]
+    fn test_minimize_calories() {
+        let day = registry::day(Puzzle::new(2022, 1)).unwrap();
+        let config = Config::default();
+
+        // the third elf carries more than a narrow total holds, which no smaller input than its own block shows
+        let input = format!("1000\n2000\n\n3000\n\n{}\n1\n\n\n4000\n5000\n", u64::MAX);
+        let args = Args::default();
+        let failure = Failure::of(&run_solver(day, &config, &args, &input), None).unwrap();
+
+        let minimized = minimize(day, &config, &input, |candidate| {
+            failure.matches(&run_solver(day, &config, &args, candidate))
+        })
+        .unwrap();
+
+        assert_eq!(minimized, format!("{}\n1\n", u64::MAX));
+    }
+
+    #[test]
+    fn test_elf_blocks() {
+        let calories = lines("1000\n2000\n\n\n3000\n\n4000\n5000");
+
+        assert_eq!(
+            elf_blocks(&calories),
+            vec![
+                vec![0, 1, 2, 3],
+                vec![4, 5],
+                vec![5, 6, 7],
+                vec![1],
+                vec![7]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shape() {
+        assert_eq!(
+            shape("line 12, column 3: expected a digit"),
+            shape("line 4, column 10: expected a digit")
+        );
+        assert_ne!(
+            shape("line 4: elf 2 carries more than 18446744073709551615 calories"),
+            shape("line 1, column 1: expected calories, found end of input")
+        );
+    }
+
+    #[test]
+    fn test_minimize_grid() {
+        let day = registry::day(Puzzle::new(2022, 8)).unwrap();
+        let input = "30373\n25512\n65332\n33594\n35390\n";
+
+        // a stand-in for a bug showing up whenever a 9 is surrounded by other trees
+        let minimized = minimize(day, &Config::default(), input, |candidate| {
+            let rows = lines(candidate);
+            rows.len() >= 3
+                && rows[1..rows.len() - 1].iter().any(|row| {
+                    row.get(1..row.len().saturating_sub(1))
+                        .is_some_and(|inside| inside.contains('9'))
+                })
+        })
+        .unwrap();
+
+        assert_eq!(minimized.lines().count(), 3);
+        assert!(minimized.lines().all(|row| row.len() == 3));
+        assert_eq!(minimized.lines().nth(1).unwrap().chars().nth(1), Some('9'));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{fmt::Write, time::Duration};
+
+use anyhow::Context;
+use common::{Args, Budget, Config, Diagnostics, Mode, DEFAULT_EXPLAIN_LIMIT};
+use y2022_day01::{
+    inventory::Inventory,
+    partition::{self, Partition, EXACT_LIMIT},
+};
+
+use crate::cli;
+
+/// How long the exhaustive search may take without `--time-limit`.
+const DEFAULT_SEARCH_TIME: Duration = Duration::from_secs(1);
+
+/// Splits the elves of an input into teams carrying about as much as each other.
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let puzzle = cli::take_puzzle(&mut args)?;
+    let k = cli::take_parsed::<usize>(&mut args, "--teams")?
+        .filter(|&k| k > 0)
+        .context("missing --teams <k>, at least 1")?;
+    let exact_limit = cli::take_parsed(&mut args, "--exact-limit")?.unwrap_or(EXACT_LIMIT);
+
+    let args = Args::parse(args)?;
+    let diagnostics = Diagnostics::new(match args.lenient {
+        true => Mode::Lenient,
+        false => Mode::Strict,
+    });
+
+    let parser = match (puzzle.year, puzzle.day) {
+        (2022, 1) => {
+            let input = cli::read_input(puzzle, &args)?;
+            let mut parser =
+                y2022_day01::parser(&Config::load(puzzle, &args)?, 1)?.with_inventory();
+
+            let parsed = input
+                .lines()
+                .try_for_each(|line| diagnostics.recover(parser.parse(line)).map(|_| ()))
+                .and_then(|()| parser.finish());
+
+            if let Some(summary) =
+                diagnostics.summary(args.explain_limit.unwrap_or(DEFAULT_EXPLAIN_LIMIT))
+            {
+                eprintln!("{summary}");
+            }
+            parsed?;
+
+            parser
+        }
+        _ => anyhow::bail!("{puzzle} has no teams to split"),
+    };
+
+    let inventory = parser.inventory().context("the parser kept no inventory")?;
+
+    let elves = inventory
+        .elves()
+        .iter()
+        .map(|elf| (elf.index, elf.total))
+        .collect::<Vec<_>>();
+    // the exhaustive search can take long for some numbers of elves and teams
+    let budget = Budget::new(
+        Some(args.time_limit.unwrap_or(DEFAULT_SEARCH_TIME)),
+        args.memory_limit,
+    );
+    print!(
+        "{}",
+        report(
+            &partition::partition(&elves, k, exact_limit, &budget),
+            inventory
+        )
+    );
+
+    Ok(())
+}
+
+fn report(partition: &Partition, inventory: &Inventory) -> String {
+    let mut report = String::new();
+
+    for (i, team) in partition.teams.iter().enumerate() {
+        let elves = team
+            .elves
+            .iter()
+            .filter_map(|&elf| inventory.get(elf))
+            .map(|elf| elf.name())
+            .collect::<Vec<_>>();
+
+        writeln!(
+            report,
+            "team {}: {} calories from {}",
+            i + 1,
+            team.calories,
+            elves.join(", ")
+        )
+        .unwrap();
+    }
+
+    let quality = match (partition.optimal, partition.interrupted) {
+        (true, _) => "optimal",
+        (false, true) => "heuristic, the exact search ran out of budget",
+        (false, false) => "heuristic",
+    };
+    writeln!(
+        report,
+        "heaviest team: {} calories, {quality}, {:.2}% above the lower bound of {}",
+        partition.largest(),
+        partition.gap() * 100.0,
+        partition.lower_bound
+    )
+    .unwrap();
+
+    report
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let mut parser = y2022_day01::parser(&Config::default(), 1)
+            .unwrap()
+            .with_inventory();
+        for line in "6000\n\n4000\n\n# Elrond\n11000\n\n# Legolas\n24000\n\n10000".lines() {
+            parser.parse(line).unwrap();
+        }
+        parser.finish().unwrap();
+
+        let inventory = parser.inventory().unwrap();
+        let elves = inventory
+            .elves()
+            .iter()
+            .map(|elf| (elf.index, elf.total))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            report(
+                &partition::partition(&elves, 2, EXACT_LIMIT, &Budget::unlimited()),
+                inventory
+            ),
+            "team 1: 28000 calories from 2nd elf, Legolas\n\
+             team 2: 27000 calories from 1st elf, Elrond, 5th elf\n\
+             heaviest team: 28000 calories, optimal, 1.82% above the lower bound of 27500\n"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use common::{Args, Context, Implementation, Puzzle, DEFAULT_IMPLEMENTATION};
+
+use crate::{
+    generate::{self, Generator},
+    minimize::{self, Minimizer},
+};
+
+/// Runs a day's parsers over an input without computing the answers.
+pub type Validator = fn(&Context, &str) -> anyhow::Result<()>;
+
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    /// what the input of the day looks like
+    pub format: &'static str,
+    /// the first one is the default
+    pub implementations: &'static [Implementation],
+    pub validate: Validator,
+    pub generate: Generator,
+    pub minimize: Minimizer,
+    /// records in a generated input that solves in about a millisecond
+    pub sample_size: usize,
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2022,
+        number: 1,
+        format: "calorie blocks",
+        implementations: y2022_day01::IMPLEMENTATIONS,
+        validate: y2022_day01::validate,
+        generate: generate::calories,
+        minimize: minimize::calories,
+        sample_size: 1000,
+    },
+    Day {
+        year: 2022,
+        number: 2,
+        format: "`A X` strategy guide",
+        implementations: &[Implementation::new(
+            DEFAULT_IMPLEMENTATION,
+            y2022_day02::solve,
+        )],
+        validate: y2022_day02::validate,
+        generate: generate::strategy_guide,
+        minimize: minimize::lines,
+        sample_size: 2000,
+    },
+    Day {
+        year: 2022,
+        number: 3,
+        format: "rucksack items",
+        implementations: &[Implementation::new(
+            DEFAULT_IMPLEMENTATION,
+            y2022_day03::solve,
+        )],
+        validate: y2022_day03::validate,
+        generate: generate::rucksacks,
+        minimize: minimize::rucksacks,
+        sample_size: 300,
+    },
+    Day {
+        year: 2022,
+        number: 4,
+        format: "`2-4,6-8` section assignments",
+        implementations: &[Implementation::new(
+            DEFAULT_IMPLEMENTATION,
+            y2022_day04::solve,
+        )],
+        validate: y2022_day04::validate,
+        generate: generate::assignments,
+        minimize: minimize::lines,
+        sample_size: 1000,
+    },
+    Day {
+        year: 2022,
+        number: 5,
+        format: "stack drawing and moves",
+        implementations: y2022_day05::IMPLEMENTATIONS,
+        validate: y2022_day05::validate,
+        generate: generate::crate_moves,
+        minimize: minimize::crate_moves,
+        sample_size: 500,
+    },
+    Day {
+        year: 2022,
+        number: 6,
+        format: "datastream",
+        implementations: y2022_day06::IMPLEMENTATIONS,
+        validate: y2022_day06::validate,
+        generate: generate::datastream,
+        minimize: minimize::datastream,
+        sample_size: 4096,
+    },
+    Day {
+        year: 2022,
+        number: 7,
+        format: "`$ cd` terminal transcript",
+        implementations: &[Implementation::new(
+            DEFAULT_IMPLEMENTATION,
+            y2022_day07::solve,
+        )],
+        validate: y2022_day07::validate,
+        generate: generate::transcript,
+        minimize: minimize::transcript,
+        sample_size: 250,
+    },
+    Day {
+        year: 2022,
+        number: 8,
+        format: "digit grid",
+        implementations: y2022_day08::IMPLEMENTATIONS,
+        validate: y2022_day08::validate,
+        generate: generate::grid,
+        minimize: minimize::grid,
+        sample_size: 16,
+    },
+    Day {
+        year: 2022,
+        number: 9,
+        format: "`R 4` motions",
+        implementations: &[Implementation::new(
+            DEFAULT_IMPLEMENTATION,
+            y2022_day09::solve,
+        )],
+        validate: y2022_day09::validate,
+        generate: generate::motions,
+        minimize: minimize::lines,
+        sample_size: 500,
+    },
+];
+
+impl Day {
+    pub fn puzzle(&self) -> Puzzle {
+        Puzzle::new(self.year, self.number)
+    }
+
+    /// The implementations picked with `--impl`, all of them if none was.
+    pub fn implementations(&self, args: &Args) -> anyhow::Result<Vec<Implementation>> {
+        match &args.implementation {
+            Some(name) => Ok(vec![common::select(self.implementations, Some(name))?]),
+            None => Ok(self.implementations.to_vec()),
+        }
+    }
+}
+
+pub fn day(puzzle: Puzzle) -> anyhow::Result<&'static Day> {
+    DAYS.iter()
+        .find(|day| day.puzzle() == puzzle)
+        .ok_or_else(|| anyhow::anyhow!("{puzzle} isn't solved yet"))
+}
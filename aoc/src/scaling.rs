@@ -0,0 +1,239 @@
+use std::time::{Duration, Instant};
+
+use common::{Args, Budget, Config, Context, Diagnostics, Explain, Puzzle, Solver, LATEST_YEAR};
+
+use crate::{
+    cli,
+    generate::Rng,
+    registry::{self, Day, DAYS},
+};
+
+/// how many times the sample size of a day is doubled
+pub const DEFAULT_STEPS: usize = 6;
+
+pub struct Measurement {
+    pub records: usize,
+    pub bytes: usize,
+    /// the fastest of the repeated runs
+    pub time: Duration,
+}
+
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let year = cli::take_parsed::<u16>(&mut args, "--year")?;
+    let days = match cli::take_option(&mut args, "--day")? {
+        Some(day) => vec![registry::day(Puzzle::new(
+            year.unwrap_or(LATEST_YEAR),
+            cli::parse_day(&day)?,
+        ))?],
+        None => DAYS
+            .iter()
+            .filter(|day| year.is_none_or(|year| day.year == year))
+            .collect(),
+    };
+    let steps = cli::take_parsed(&mut args, "--steps")?.unwrap_or(DEFAULT_STEPS);
+    let repeat = cli::take_parsed(&mut args, "--repeat")?.unwrap_or(3);
+    let seed = cli::take_parsed(&mut args, "--seed")?.unwrap_or(2022);
+    let max_exponent = cli::take_parsed::<f64>(&mut args, "--max-exponent")?;
+    let csv = match cli::take_option(&mut args, "--format")?.as_deref() {
+        None | Some("table") => false,
+        Some("csv") => true,
+        Some(format) => anyhow::bail!("unknown format {format:?}, expected table or csv"),
+    };
+
+    let args = Args::parse(args)?;
+
+    match csv {
+        true => println!("year,day,impl,records,bytes,seconds,exponent"),
+        false => println!(
+            "{:>4}  {:>3}  {:<10}  {:>8}  {:>10}  {:>12}",
+            "year", "day", "impl", "records", "bytes", "time"
+        ),
+    }
+
+    let mut too_steep = Vec::new();
+
+    for day in days {
+        let config = Config::load(day.puzzle(), &args)?;
+
+        for implementation in day.implementations(&args)? {
+            // every implementation is timed on the same inputs
+            let mut rng = Rng::new(seed);
+
+            let measurements = (0..steps)
+                .map(|step| {
+                    measure(
+                        day,
+                        implementation.solve,
+                        &config,
+                        &args,
+                        day.sample_size << step,
+                        repeat,
+                        &mut rng,
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let exponent = fit_exponent(&measurements);
+            let shown = exponent.map_or("-".to_string(), |exponent| format!("{exponent:.2}"));
+
+            for m in &measurements {
+                match csv {
+                    true => println!(
+                        "{},{},{},{},{},{:.9},{shown}",
+                        day.year,
+                        day.number,
+                        implementation.name,
+                        m.records,
+                        m.bytes,
+                        m.time.as_secs_f64()
+                    ),
+                    false => println!(
+                        "{:>4}  {:>3}  {:<10}  {:>8}  {:>10}  {:>12}",
+                        day.year,
+                        day.number,
+                        implementation.name,
+                        m.records,
+                        m.bytes,
+                        format!("{:.2?}", m.time)
+                    ),
+                }
+            }
+
+            if !csv {
+                println!("           time grows as size^{shown}");
+            }
+
+            if let (Some(exponent), Some(max)) = (exponent, max_exponent) {
+                if exponent > max {
+                    too_steep.push(format!(
+                        "{} {} ({exponent:.2})",
+                        day.puzzle(),
+                        implementation.name
+                    ));
+                }
+            }
+        }
+    }
+
+    match too_steep.is_empty() {
+        true => Ok(()),
+        false => anyhow::bail!(
+            "growth above size^{} for {}",
+            max_exponent.unwrap_or_default(),
+            too_steep.join(", ")
+        ),
+    }
+}
+
+/// Times an implementation of the day on a generated input of `records` records.
+pub fn measure(
+    day: &Day,
+    solve: Solver,
+    config: &Config,
+    args: &Args,
+    records: usize,
+    repeat: usize,
+    rng: &mut Rng,
+) -> anyhow::Result<Measurement> {
+    let input = (day.generate)(rng, records);
+    let mut time = Duration::MAX;
+
+    for _ in 0..repeat.max(1) {
+        let context = Context {
+            config: config.clone(),
+            explain: Explain::disabled(),
+            budget: Budget::new(args.time_limit, args.memory_limit),
+            diagnostics: Diagnostics::strict(),
+        };
+
+        let started = Instant::now();
+        solve(&context, &input)?;
+        time = time.min(started.elapsed());
+    }
+
+    Ok(Measurement {
+        records,
+        bytes: input.len(),
+        time,
+    })
+}
+
+/// The slope of a least squares line through the `(log bytes, log time)` points,
+/// so 1 means linear in the size of the input and 2 quadratic.
+pub fn fit_exponent(measurements: &[Measurement]) -> Option<f64> {
+    let points = measurements
+        .iter()
+        .filter(|m| m.bytes > 0 && !m.time.is_zero())
+        .map(|m| ((m.bytes as f64).ln(), m.time.as_secs_f64().ln()))
+        .collect::<Vec<_>>();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn measurement(bytes: usize, nanos: u64) -> Measurement {
+        Measurement {
+            records: bytes,
+            bytes,
+            time: Duration::from_nanos(nanos),
+        }
+    }
+
+    #[test]
+    fn test_fit_exponent() {
+        let linear = [
+            measurement(100, 1000),
+            measurement(200, 2000),
+            measurement(400, 4000),
+        ];
+        assert!((fit_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+
+        let quadratic = [
+            measurement(10, 100),
+            measurement(20, 400),
+            measurement(40, 1600),
+        ];
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+
+        assert_eq!(fit_exponent(&[measurement(10, 100)]), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = registry::day(Puzzle::new(2022, 4)).unwrap();
+        let mut rng = Rng::new(1);
+
+        let m = measure(
+            day,
+            day.implementations[0].solve,
+            &Config::default(),
+            &Args::default(),
+            10,
+            1,
+            &mut rng,
+        )
+        .unwrap();
+        assert_eq!(m.records, 10);
+        assert!(m.bytes >= 10 * "1-1,1-1\n".len());
+    }
+}
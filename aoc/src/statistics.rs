@@ -0,0 +1,87 @@
+use std::{fs::File, io::BufReader};
+
+use anyhow::Context;
+use common::{Args, Config, Diagnostics, Mode, DEFAULT_EXPLAIN_LIMIT};
+use y2022_day01::statistics::Statistics;
+
+use crate::cli;
+
+const DEFAULT_PERCENTILES: &[f64] = &[10.0, 25.0, 75.0, 90.0, 99.0];
+const DEFAULT_BINS: usize = 10;
+
+/// Summarizes the calories the elves of an input carry, reading it one line at a time.
+pub fn run(mut args: Vec<String>) -> anyhow::Result<()> {
+    let puzzle = cli::take_puzzle(&mut args)?;
+    let percentiles = cli::take_option(&mut args, "--percentiles")?
+        .map(|percentiles| parse_percentiles(&percentiles))
+        .transpose()?
+        .unwrap_or_else(|| DEFAULT_PERCENTILES.to_vec());
+    let bins = cli::take_parsed(&mut args, "--bins")?.unwrap_or(DEFAULT_BINS);
+    // a sketch keeps the memory bounded however many elves there are, at the cost of exact percentiles
+    let statistics = match cli::take_parsed::<f64>(&mut args, "--sketch")? {
+        Some(accuracy) if accuracy > 0.0 && accuracy < 1.0 => Statistics::streaming(accuracy),
+        Some(accuracy) => anyhow::bail!("invalid --sketch {accuracy}, expected between 0 and 1"),
+        None => Statistics::exact(),
+    };
+
+    let args = Args::parse(args)?;
+    let diagnostics = Diagnostics::new(match args.lenient {
+        true => Mode::Lenient,
+        false => Mode::Strict,
+    });
+
+    let statistics = match (puzzle.year, puzzle.day) {
+        (2022, 1) => {
+            let path = cli::input_path(puzzle, &args);
+            let file = File::open(&path).context(format!("failed to read {}", path.display()))?;
+            let parser = y2022_day01::parser(&Config::load(puzzle, &args)?, 1)?
+                .with_statistics(statistics)
+                .read_from(BufReader::new(file), &diagnostics);
+
+            if let Some(summary) =
+                diagnostics.summary(args.explain_limit.unwrap_or(DEFAULT_EXPLAIN_LIMIT))
+            {
+                eprintln!("{summary}");
+            }
+
+            parser?
+                .statistics()
+                .cloned()
+                .context("the parser kept no statistics")?
+        }
+        _ => anyhow::bail!("{puzzle} has no statistics"),
+    };
+
+    print!("{}", statistics.report(&percentiles, bins));
+
+    Ok(())
+}
+
+/// `10,50,99.9`, each from 0 to 100.
+fn parse_percentiles(percentiles: &str) -> anyhow::Result<Vec<f64>> {
+    percentiles
+        .split(',')
+        .map(|p| {
+            p.trim()
+                .parse()
+                .ok()
+                .filter(|p| (0.0..=100.0).contains(p))
+                .context(format!("invalid percentile {p:?}, expected 0 to 100"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_percentiles() {
+        assert_eq!(
+            parse_percentiles("10, 50,99.9").unwrap(),
+            vec![10.0, 50.0, 99.9]
+        );
+        assert!(parse_percentiles("101").is_err());
+        assert!(parse_percentiles("10,,50").is_err());
+    }
+}
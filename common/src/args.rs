@@ -0,0 +1,184 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+
+use crate::{
+    budget::{parse_bytes, parse_duration},
+    Feedback,
+};
+
+/// Command line arguments shared by all days.
+#[derive(Debug, Default)]
+pub struct Args {
+    /// `--input <path>`, defaults to `input.txt`
+    pub input: Option<PathBuf>,
+    /// `--config <path>`, otherwise `aoc.toml` is searched for from the current directory up
+    pub config: Option<PathBuf>,
+    /// `--set <key>=<value>`, for example `--set part2.knots=20`
+    pub overrides: Vec<(String, String)>,
+    /// `--explain`
+    pub explain: bool,
+    /// `--explain-limit <entries>`, implies `--explain`
+    pub explain_limit: Option<usize>,
+    /// `--time-limit <duration>`, for example `2s` or `500ms`
+    pub time_limit: Option<Duration>,
+    /// `--memory-limit <bytes>`, for example `512M`
+    pub memory_limit: Option<usize>,
+    /// `--lenient` skips bad records instead of failing, `--strict` is the default
+    pub lenient: bool,
+    /// `--journal <path>`, defaults to `journal.toml` next to the input
+    pub journal: Option<PathBuf>,
+    /// `--feedback <part>=<feedback>` records the computed answer in the journal,
+    /// for example `--feedback 1=too-low`
+    pub feedback: Vec<(u8, Feedback)>,
+    /// `--impl <name>` picks one of the implementations of a day, the first one by default
+    pub implementation: Option<String>,
+}
+
+impl Args {
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut result = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().context("missing path after --input")?;
+                    result.input = Some(PathBuf::from(path));
+                }
+                "--config" => {
+                    let path = args.next().context("missing path after --config")?;
+                    result.config = Some(PathBuf::from(path));
+                }
+                "--set" => {
+                    let value = args.next().context("missing key=value after --set")?;
+                    let (key, value) = value
+                        .split_once('=')
+                        .context(format!("expected key=value after --set, found {value:?}"))?;
+
+                    result
+                        .overrides
+                        .push((key.trim().to_string(), value.trim().to_string()));
+                }
+                "--explain" => result.explain = true,
+                "--strict" => result.lenient = false,
+                "--lenient" => result.lenient = true,
+                "--explain-limit" => {
+                    let limit = args
+                        .next()
+                        .context("missing number after --explain-limit")?;
+                    result.explain = true;
+                    result.explain_limit = Some(
+                        limit
+                            .parse()
+                            .context(format!("invalid --explain-limit {limit:?}"))?,
+                    );
+                }
+                "--time-limit" => {
+                    let limit = args.next().context("missing duration after --time-limit")?;
+                    result.time_limit = Some(
+                        parse_duration(&limit)
+                            .context(format!("invalid --time-limit {limit:?}"))?,
+                    );
+                }
+                "--memory-limit" => {
+                    let limit = args.next().context("missing size after --memory-limit")?;
+                    result.memory_limit = Some(
+                        parse_bytes(&limit).context(format!("invalid --memory-limit {limit:?}"))?,
+                    );
+                }
+                "--impl" => {
+                    let name = args.next().context("missing name after --impl")?;
+                    result.implementation = Some(name);
+                }
+                "--journal" => {
+                    let path = args.next().context("missing path after --journal")?;
+                    result.journal = Some(PathBuf::from(path));
+                }
+                "--feedback" => {
+                    let value = args
+                        .next()
+                        .context("missing part=feedback after --feedback")?;
+                    let (part, feedback) = value.split_once('=').context(format!(
+                        "expected part=feedback after --feedback, found {value:?}"
+                    ))?;
+
+                    let part = part
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|part| [1, 2].contains(part))
+                        .context(format!("invalid part in --feedback {value:?}"))?;
+                    result.feedback.push((part, feedback.trim().parse()?));
+                }
+                _ => anyhow::bail!("unknown argument {arg:?}"),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> anyhow::Result<Args> {
+        Args::parse(values.iter().map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = args(&["--set", "part1.top_n = 5", "--config", "other.toml"]).unwrap();
+
+        assert_eq!(args.config, Some(PathBuf::from("other.toml")));
+        assert_eq!(
+            args.overrides,
+            vec![("part1.top_n".to_string(), "5".to_string())]
+        );
+        assert!(!args.explain);
+
+        let args = super::tests::args(&["--explain-limit", "3"]).unwrap();
+        assert!(args.explain);
+        assert_eq!(args.explain_limit, Some(3));
+
+        let args = super::tests::args(&[
+            "--time-limit",
+            "2s",
+            "--memory-limit",
+            "64M",
+            "--input",
+            "a.txt",
+        ])
+        .unwrap();
+        assert_eq!(args.time_limit, Some(Duration::from_secs(2)));
+        assert_eq!(args.memory_limit, Some(64 << 20));
+        assert_eq!(args.input, Some(PathBuf::from("a.txt")));
+        assert!(!args.lenient);
+
+        let args = super::tests::args(&["--strict", "--lenient"]).unwrap();
+        assert!(args.lenient);
+
+        let args = super::tests::args(&["--feedback", "2=too-low", "--journal", "j.toml"]).unwrap();
+        assert_eq!(args.feedback, vec![(2, Feedback::TooLow)]);
+        assert_eq!(args.journal, Some(PathBuf::from("j.toml")));
+
+        let args = super::tests::args(&["--impl", "stacks"]).unwrap();
+        assert_eq!(args.implementation.as_deref(), Some("stacks"));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(args(&["--set"]).is_err());
+        assert!(args(&["--set", "top_n"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+        assert!(args(&["--explain-limit", "many"]).is_err());
+        assert!(args(&["--time-limit", "forever"]).is_err());
+        assert!(args(&["--feedback", "3=wrong"]).is_err());
+        assert!(args(&["--feedback", "1=close"]).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    error::Error,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// the clock is only read every this many checks, reading it on every crate moved adds up
+const CLOCK_INTERVAL: u64 = 1024;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts the bytes currently allocated so memory budgets can be enforced.
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+/// Installs [`TrackingAllocator`] as the global allocator, so memory budgets can be enforced.
+///
+/// Called once by each binary, leaving libraries linking this crate with their own allocator.
+#[macro_export]
+macro_rules! track_allocations {
+    () => {
+        #[global_allocator]
+        static ALLOCATOR: $crate::TrackingAllocator = $crate::TrackingAllocator;
+    };
+}
+
+/// The number of bytes currently allocated on the heap.
+pub fn allocated_bytes() -> usize {
+    ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// Wall-time and memory limits shared between the runner and a solver.
+///
+/// Solvers call [`Budget::check`] from their main loops and stop with a [`BudgetExceeded`]
+/// error once a limit is reached or the budget has been cancelled.
+/// Clones share the same limits and cancellation flag.
+#[derive(Clone)]
+pub struct Budget {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    started: Instant,
+    time_limit: Option<Duration>,
+    memory_limit: Option<usize>,
+    cancelled: AtomicBool,
+    checks: AtomicU64,
+}
+
+impl Budget {
+    pub fn new(time_limit: Option<Duration>, memory_limit: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                started: Instant::now(),
+                time_limit,
+                memory_limit,
+                cancelled: AtomicBool::new(false),
+                checks: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(None, None)
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.inner.started.elapsed()
+    }
+
+    /// Fails if the budget is spent. `progress` describes how far the solver got.
+    pub fn check(&self, progress: impl FnOnce() -> String) -> Result<(), BudgetExceeded> {
+        let inner = &self.inner;
+
+        let limit = if inner.cancelled.load(Ordering::Relaxed) {
+            Some(Limit::Cancelled)
+        } else if inner
+            .memory_limit
+            .is_some_and(|limit| allocated_bytes() > limit)
+        {
+            inner.memory_limit.map(Limit::Memory)
+        } else if inner.time_limit.is_some()
+            && inner
+                .checks
+                .fetch_add(1, Ordering::Relaxed)
+                .is_multiple_of(CLOCK_INTERVAL)
+        {
+            inner
+                .time_limit
+                .filter(|limit| inner.started.elapsed() > *limit)
+                .map(Limit::Time)
+        } else {
+            None
+        };
+
+        match limit {
+            Some(limit) => Err(BudgetExceeded {
+                limit,
+                elapsed: inner.started.elapsed(),
+                allocated: allocated_bytes(),
+                progress: progress(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Time(Duration),
+    Memory(usize),
+    Cancelled,
+}
+
+#[derive(Debug)]
+pub struct BudgetExceeded {
+    pub limit: Limit,
+    pub elapsed: Duration,
+    pub allocated: usize,
+    pub progress: String,
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.limit {
+            Limit::Time(limit) => write!(f, "budget exceeded: time limit of {limit:?} reached")?,
+            Limit::Memory(limit) => write!(
+                f,
+                "budget exceeded: memory limit of {} reached with {} allocated",
+                format_bytes(limit),
+                format_bytes(self.allocated)
+            )?,
+            Limit::Cancelled => write!(f, "budget exceeded: cancelled")?,
+        }
+
+        write!(
+            f,
+            " after {:.2?}, stopped at {}",
+            self.elapsed, self.progress
+        )
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// Parses `1.5`, `1.5s`, `300ms` or `2m`, a bare number being seconds.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let (number, unit) = split_unit(value);
+    let number = number.parse::<f64>().ok().filter(|n| *n >= 0.0)?;
+
+    let seconds = match unit {
+        "" | "s" => number,
+        "ms" => number / 1000.0,
+        "m" => number * 60.0,
+        _ => return None,
+    };
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Parses `1048576`, `512K`, `64M` or `2G`, with binary multiples.
+pub fn parse_bytes(value: &str) -> Option<usize> {
+    let (number, unit) = split_unit(value);
+    let number = number.parse::<usize>().ok()?;
+
+    let multiplier = match unit.to_ascii_uppercase().trim_end_matches("IB") {
+        "" | "B" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return None,
+    };
+
+    number.checked_mul(multiplier)
+}
+
+fn split_unit(value: &str) -> (&str, &str) {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+
+    value.split_at(split)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GiB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1u64 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1u64 << 10) as f64),
+        b => format!("{b} B"),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    crate::track_allocations!();
+
+    #[test]
+    fn test_time_limit() {
+        let budget = Budget::new(Some(Duration::ZERO), None);
+        std::thread::sleep(Duration::from_millis(1));
+
+        let error = budget.check(|| "line 3".to_string()).unwrap_err();
+        assert_eq!(error.limit, Limit::Time(Duration::ZERO));
+        assert!(error.to_string().ends_with("stopped at line 3"));
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let budget = Budget::new(None, Some(allocated_bytes() + (1 << 20)));
+        let buffer = vec![0u8; 4 << 20];
+
+        let error = budget.check(|| "move 2".to_string()).unwrap_err();
+        assert!(matches!(error.limit, Limit::Memory(_)));
+        assert!(error.allocated >= buffer.len());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let budget = Budget::unlimited();
+        budget.check(|| unreachable!()).unwrap();
+
+        budget.clone().cancel();
+
+        let error = budget.check(|| "step 7".to_string()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "budget exceeded: cancelled after {:.2?}, stopped at step 7",
+                error.elapsed
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_duration("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("300ms"), Some(Duration::from_millis(300)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("soon"), None);
+
+        assert_eq!(parse_bytes("512"), Some(512));
+        assert_eq!(parse_bytes("64M"), Some(64 << 20));
+        assert_eq!(parse_bytes("2GiB"), Some(2 << 30));
+        assert_eq!(parse_bytes("lots"), None);
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+
+use crate::{Args, Puzzle};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Puzzle parameters of a single day.
+///
+/// Values are looked up in `[yYYYY.dayNN.partN]` first and then in `[yYYYY.dayNN]`,
+/// so parameters shared by both parts can be set once.
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    day: Table,
+}
+
+impl Config {
+    /// Reads the configuration file and applies the `--set` overrides on top of it.
+    pub fn load(puzzle: Puzzle, args: &Args) -> anyhow::Result<Self> {
+        let path = match &args.config {
+            Some(path) => Some(path.clone()),
+            None => find_config_file(&std::env::current_dir()?),
+        };
+
+        let table = match path {
+            Some(path) => fs::read_to_string(&path)
+                .context(format!("failed to read {}", path.display()))?
+                .parse::<Table>()
+                .context(format!("failed to parse {}", path.display()))?,
+            None => Table::new(),
+        };
+
+        Self::new(puzzle, table, &args.overrides)
+    }
+
+    pub fn new(
+        puzzle: Puzzle,
+        mut table: Table,
+        overrides: &[(String, String)],
+    ) -> anyhow::Result<Self> {
+        let mut season = match table.remove(&puzzle.season()) {
+            Some(Value::Table(season)) => season,
+            Some(_) => anyhow::bail!("`{}` must be a table", puzzle.season()),
+            None => Table::new(),
+        };
+
+        let mut day = match season.remove(&puzzle.name()) {
+            Some(Value::Table(day)) => day,
+            Some(_) => anyhow::bail!("`{}.{}` must be a table", puzzle.season(), puzzle.name()),
+            None => Table::new(),
+        };
+
+        for (key, value) in overrides {
+            set(&mut day, key, parse_value(value))?;
+        }
+
+        Ok(Self { day })
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, part: u8, key: &str, default: T) -> anyhow::Result<T> {
+        let value = self
+            .day
+            .get(&format!("part{part}"))
+            .and_then(|part| part.get(key))
+            .or_else(|| self.day.get(key));
+
+        match value {
+            Some(value) => value
+                .clone()
+                .try_into()
+                .context(format!("invalid value for `{key}` in part {part}")),
+            None => Ok(default),
+        }
+    }
+
+    /// Like `get`, for counts and sizes that make no sense below `min`.
+    pub fn get_at_least(
+        &self,
+        part: u8,
+        key: &str,
+        default: usize,
+        min: usize,
+    ) -> anyhow::Result<usize> {
+        match self.get(part, key, default)? {
+            value if value < min => {
+                anyhow::bail!(
+                    "invalid value {value} for `{key}` in part {part}, expected at least {min}"
+                )
+            }
+            value => Ok(value),
+        }
+    }
+}
+
+fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Override values are TOML literals, anything that doesn't parse as one is taken as a string.
+fn parse_value(value: &str) -> Value {
+    format!("value = {value}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(value.to_string()))
+}
+
+fn set(table: &mut Table, key: &str, value: Value) -> anyhow::Result<()> {
+    match key.split_once('.') {
+        Some((section, key)) => {
+            let section = table
+                .entry(section)
+                .or_insert_with(|| Value::Table(Table::new()));
+
+            match section {
+                Value::Table(section) => set(section, key, value),
+                _ => anyhow::bail!("`{key}` can't be set because its parent isn't a table"),
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[y2022.day07]
+total_disk_space = 70000000
+part1 = { top_limit = 100000 }
+part2 = { free_space_required = 30000000, total_disk_space = 80000000 }
+
+[y2022.day09]
+part2 = { knots = 10 }
+
+[y2023.day07]
+total_disk_space = 5
+"#;
+
+    fn load(day: u8, overrides: &[(&str, &str)]) -> Config {
+        let overrides = overrides
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<Vec<_>>();
+
+        Config::new(Puzzle::new(2022, day), CONFIG.parse().unwrap(), &overrides).unwrap()
+    }
+
+    #[test]
+    fn test_get() {
+        let config = load(7, &[]);
+
+        assert_eq!(config.get(1, "top_limit", 0).unwrap(), 100000);
+        assert_eq!(config.get(1, "total_disk_space", 0).unwrap(), 70000000);
+        assert_eq!(config.get(2, "total_disk_space", 0).unwrap(), 80000000);
+        assert_eq!(config.get(2, "top_limit", 5).unwrap(), 5);
+
+        assert!(config.get(1, "top_limit", String::new()).is_err());
+
+        // every season has its own parameters
+        let config = Config::new(Puzzle::new(2023, 7), CONFIG.parse().unwrap(), &[]).unwrap();
+        assert_eq!(config.get(1, "total_disk_space", 0).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let config = load(9, &[("part2.knots", "20"), ("part1.knots", "3")]);
+
+        assert_eq!(config.get(1, "knots", 2).unwrap(), 3);
+        assert_eq!(config.get(2, "knots", 10).unwrap(), 20);
+
+        let config = load(9, &[("name", "rope")]);
+        assert_eq!(config.get(1, "name", String::new()).unwrap(), "rope");
+
+        let config = load(1, &[]);
+        assert_eq!(config.get(1, "top_n", 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_get_at_least() {
+        let config = load(9, &[("part2.knots", "0")]);
+
+        assert_eq!(config.get_at_least(1, "knots", 2, 1).unwrap(), 2);
+        assert_eq!(
+            config
+                .get_at_least(2, "knots", 10, 1)
+                .unwrap_err()
+                .to_string(),
+            "invalid value 0 for `knots` in part 2, expected at least 1"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::cell::RefCell;
+
+use parsing::ParseError;
+
+/// What happens to a record of the input that fails to parse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// the first bad record stops the solver
+    #[default]
+    Strict,
+    /// bad records are skipped and reported as warnings
+    Lenient,
+}
+
+/// Collects the records skipped in lenient mode.
+///
+/// Only parse errors can be skipped, anything else such as an exceeded budget still stops the solver.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    mode: Mode,
+    warnings: RefCell<Vec<ParseError>>,
+}
+
+impl Diagnostics {
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            warnings: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn strict() -> Self {
+        Self::new(Mode::Strict)
+    }
+
+    pub fn lenient() -> Self {
+        Self::new(Mode::Lenient)
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Turns a parse error into a warning in lenient mode, `None` meaning the record should be skipped.
+    pub fn recover<T, E: Into<anyhow::Error>>(
+        &self,
+        result: Result<T, E>,
+    ) -> anyhow::Result<Option<T>> {
+        let error = match result {
+            Ok(value) => return Ok(Some(value)),
+            Err(error) => error.into(),
+        };
+
+        if self.mode == Mode::Strict {
+            return Err(error);
+        }
+
+        let error = error.downcast::<ParseError>()?;
+        let mut warnings = self.warnings.borrow_mut();
+
+        // several parsers reading the same record fail the same way, it's only worth one warning
+        if warnings.last() != Some(&error) {
+            warnings.push(error);
+        }
+
+        Ok(None)
+    }
+
+    pub fn warnings(&self) -> Vec<ParseError> {
+        self.warnings.borrow().clone()
+    }
+
+    /// A summary of the skipped records, showing at most `limit` of them.
+    pub fn summary(&self, limit: usize) -> Option<String> {
+        let warnings = self.warnings.borrow();
+
+        if warnings.is_empty() {
+            return None;
+        }
+
+        let mut summary = match warnings.len() {
+            1 => "warning: skipped 1 bad record".to_string(),
+            count => format!("warning: skipped {count} bad records"),
+        };
+
+        for warning in warnings.iter().take(limit) {
+            summary.push_str(&format!("\n  {warning}"));
+        }
+
+        if warnings.len() > limit {
+            summary.push_str(&format!("\n  ... and {} more", warnings.len() - limit));
+        }
+
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::Budget;
+
+    use super::*;
+
+    fn bad_record(line: usize) -> Result<u32, ParseError> {
+        Err(ParseError::new(1, "a number", "\"x\"").at_line(line))
+    }
+
+    #[test]
+    fn test_strict() {
+        let diagnostics = Diagnostics::strict();
+
+        assert_eq!(
+            diagnostics.recover(Ok::<_, ParseError>(1)).unwrap(),
+            Some(1)
+        );
+
+        let error = diagnostics.recover(bad_record(3)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 3, column 1: expected a number, found "x""#
+        );
+        assert_eq!(diagnostics.summary(10), None);
+    }
+
+    #[test]
+    fn test_lenient() {
+        let diagnostics = Diagnostics::lenient();
+
+        assert_eq!(diagnostics.recover(bad_record(2)).unwrap(), None);
+        assert_eq!(diagnostics.recover(bad_record(2)).unwrap(), None);
+        assert_eq!(diagnostics.recover(bad_record(5)).unwrap(), None);
+        assert_eq!(diagnostics.recover(bad_record(7)).unwrap(), None);
+
+        assert_eq!(diagnostics.warnings().len(), 3);
+        assert_eq!(
+            diagnostics.summary(2).unwrap(),
+            r#"warning: skipped 3 bad records
+  line 2, column 1: expected a number, found "x"
+  line 5, column 1: expected a number, found "x"
+  ... and 1 more"#
+        );
+
+        // only parse errors can be skipped
+        let budget = Budget::unlimited();
+        budget.cancel();
+        assert!(diagnostics
+            .recover(budget.check(|| "line 8".to_string()))
+            .is_err());
+    }
+}
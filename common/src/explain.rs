@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+
+use crate::Args;
+
+/// How many entries of a single list are printed when `--explain-limit` isn't given.
+pub const DEFAULT_EXPLAIN_LIMIT: usize = 10;
+
+/// Prints a human-readable trace of how the answers were reached.
+///
+/// Everything goes to stderr so the answers on stdout stay easy to grep.
+/// Messages are built lazily, so a disabled `Explain` costs next to nothing.
+pub struct Explain {
+    limit: Option<usize>,
+    captured: Option<RefCell<Vec<String>>>,
+}
+
+impl Explain {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            limit: args
+                .explain
+                .then(|| args.explain_limit.unwrap_or(DEFAULT_EXPLAIN_LIMIT)),
+            captured: None,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            limit: None,
+            captured: None,
+        }
+    }
+
+    /// Keeps the trace in memory instead of printing it.
+    pub fn capture(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            captured: Some(RefCell::new(Vec::new())),
+        }
+    }
+
+    pub fn captured(&self) -> Vec<String> {
+        self.captured
+            .as_ref()
+            .map(|lines| lines.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.limit.is_some()
+    }
+
+    pub fn note(&self, message: impl FnOnce() -> String) {
+        if self.is_enabled() {
+            self.emit(message());
+        }
+    }
+
+    /// Starts a titled list whose entries beyond the limit are only counted.
+    pub fn list(&self, title: impl FnOnce() -> String) -> Trace<'_> {
+        self.note(|| format!("{}:", title()));
+
+        Trace {
+            explain: self,
+            shown: 0,
+            omitted: 0,
+        }
+    }
+
+    fn emit(&self, line: String) {
+        match &self.captured {
+            Some(lines) => lines.borrow_mut().push(line),
+            None => eprintln!("{line}"),
+        }
+    }
+}
+
+pub struct Trace<'a> {
+    explain: &'a Explain,
+    shown: usize,
+    omitted: usize,
+}
+
+impl Default for Explain {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl Trace<'_> {
+    pub fn entry(&mut self, message: impl FnOnce() -> String) {
+        let Some(limit) = self.explain.limit else {
+            return;
+        };
+
+        match self.shown < limit {
+            true => {
+                self.shown += 1;
+                self.explain.emit(format!("  {}", message()));
+            }
+            false => self.omitted += 1,
+        }
+    }
+}
+
+impl Drop for Trace<'_> {
+    fn drop(&mut self) {
+        if self.omitted > 0 {
+            self.explain
+                .emit(format!("  ... and {} more", self.omitted));
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_is_bounded() {
+        let explain = Explain::capture(2);
+
+        explain.note(|| "summary".to_string());
+        {
+            let mut trace = explain.list(|| "moves".to_string());
+            for i in 0..5 {
+                trace.entry(|| format!("move {i}"));
+            }
+        }
+
+        assert_eq!(
+            explain.captured(),
+            vec![
+                "summary",
+                "moves:",
+                "  move 0",
+                "  move 1",
+                "  ... and 3 more"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled() {
+        let explain = Explain::disabled();
+
+        explain.note(|| unreachable!());
+        explain.list(|| unreachable!()).entry(|| unreachable!());
+
+        assert!(explain.captured().is_empty());
+    }
+}
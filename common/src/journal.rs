@@ -0,0 +1,238 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::Answers;
+
+pub const JOURNAL_FILE: &str = "journal.toml";
+
+/// What the puzzle site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// wrong without a hint in which direction
+    Wrong,
+}
+
+impl FromStr for Feedback {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "wrong" => Ok(Self::Wrong),
+            _ => anyhow::bail!(
+                "invalid feedback {s:?}, expected correct, too-high, too-low or wrong"
+            ),
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::Wrong => "wrong",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+}
+
+/// The answers of a day submitted so far, in the order they were submitted.
+///
+/// Kept next to the input of the day so that answers already known to be wrong aren't tried again.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl Journal {
+    /// Reads a journal, a missing file being an empty one.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text =
+            fs::read_to_string(path).context(format!("failed to read {}", path.display()))?;
+
+        toml::from_str(&text).context(format!("failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .context(format!("failed to write {}", path.display()))
+    }
+
+    /// Records an attempt, replacing an earlier one with the same answer.
+    pub fn record(&mut self, part: u8, answer: &str, feedback: Feedback) {
+        self.attempts
+            .retain(|attempt| attempt.part != part || attempt.answer != answer);
+
+        self.attempts.push(Attempt {
+            part,
+            answer: answer.to_string(),
+            feedback,
+        });
+    }
+
+    pub fn attempts(&self, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.part == part)
+    }
+
+    /// Why the answer to a part shouldn't be submitted, if anything speaks against it.
+    pub fn check(&self, part: u8, answer: &str) -> Option<String> {
+        if let Some(attempt) = self.attempts(part).find(|attempt| attempt.answer == answer) {
+            return match attempt.feedback {
+                Feedback::Correct => None,
+                feedback => Some(format!(
+                    "part {part} answer {answer} was already submitted and is {feedback}"
+                )),
+            };
+        }
+
+        if let Some(correct) = self
+            .attempts(part)
+            .find(|attempt| attempt.feedback == Feedback::Correct)
+        {
+            return Some(format!(
+                "part {part} answer {answer} differs from the accepted answer {}",
+                correct.answer
+            ));
+        }
+
+        // the bounds only apply to numbers, the answers of some days are text
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |feedback| {
+            self.attempts(part)
+                .filter(move |attempt| attempt.feedback == feedback)
+                .filter_map(|attempt| attempt.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Feedback::TooHigh).min().filter(|&high| value >= high) {
+            return Some(format!(
+                "part {part} answer {answer} isn't below {high}, which is too high"
+            ));
+        }
+
+        if let Some(low) = bound(Feedback::TooLow).max().filter(|&low| value <= low) {
+            return Some(format!(
+                "part {part} answer {answer} isn't above {low}, which is too low"
+            ));
+        }
+
+        None
+    }
+
+    /// The warnings for both answers of a day.
+    pub fn check_answers(&self, answers: &Answers) -> Vec<String> {
+        [(1, &answers.part1), (2, &answers.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| self.check(part, answer))
+            .collect()
+    }
+}
+
+/// The journal given with `--journal`, otherwise `journal.toml` next to the input.
+pub fn journal_path(input: &Path, journal: Option<&Path>) -> PathBuf {
+    match journal {
+        Some(path) => path.to_path_buf(),
+        None => input.with_file_name(JOURNAL_FILE),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn journal() -> Journal {
+        let mut journal = Journal::default();
+        journal.record(1, "500", Feedback::TooHigh);
+        journal.record(1, "100", Feedback::TooLow);
+        journal.record(1, "450", Feedback::TooHigh);
+        journal.record(2, "CMZ", Feedback::Wrong);
+        journal
+    }
+
+    #[test]
+    fn test_check() {
+        let journal = journal();
+
+        assert_eq!(
+            journal.check(1, "500").unwrap(),
+            "part 1 answer 500 was already submitted and is too high"
+        );
+        assert_eq!(
+            journal.check(1, "470").unwrap(),
+            "part 1 answer 470 isn't below 450, which is too high"
+        );
+        assert_eq!(
+            journal.check(1, "42").unwrap(),
+            "part 1 answer 42 isn't above 100, which is too low"
+        );
+        assert_eq!(journal.check(1, "300"), None);
+        assert_eq!(journal.check(2, "300"), None);
+        assert_eq!(
+            journal.check(2, "CMZ").unwrap(),
+            "part 2 answer CMZ was already submitted and is wrong"
+        );
+        assert_eq!(journal.check(2, "MCD"), None);
+    }
+
+    #[test]
+    fn test_correct() {
+        let mut journal = journal();
+        journal.record(1, "300", Feedback::Correct);
+
+        assert_eq!(journal.check(1, "300"), None);
+        assert_eq!(
+            journal.check(1, "301").unwrap(),
+            "part 1 answer 301 differs from the accepted answer 300"
+        );
+        assert_eq!(
+            journal.check_answers(&Answers::new(300, "CMZ")),
+            vec!["part 2 answer CMZ was already submitted and is wrong".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-{}-journal.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(Journal::load(&path).unwrap(), Journal::default());
+
+        let mut journal = journal();
+        journal.record(1, "500", Feedback::Wrong);
+        assert_eq!(journal.attempts(1).count(), 3);
+
+        journal.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("feedback = \"too-high\""), "{text}");
+        assert_eq!(Journal::load(&path).unwrap(), journal);
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,22 @@
+mod args;
+mod budget;
+mod config;
+mod diagnostics;
+mod explain;
+mod journal;
+mod puzzle;
+mod runner;
+
+pub use args::Args;
+pub use budget::{
+    allocated_bytes, parse_bytes, parse_duration, Budget, BudgetExceeded, Limit, TrackingAllocator,
+};
+pub use config::{Config, CONFIG_FILE};
+pub use diagnostics::{Diagnostics, Mode};
+pub use explain::{Explain, Trace, DEFAULT_EXPLAIN_LIMIT};
+pub use journal::{journal_path, Attempt, Feedback, Journal, JOURNAL_FILE};
+pub use puzzle::{Puzzle, LATEST_YEAR};
+pub use runner::{
+    run, run_implementations, select, Answers, Context, Implementation, Solver,
+    DEFAULT_IMPLEMENTATION, DEFAULT_INPUT,
+};
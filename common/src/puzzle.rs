@@ -0,0 +1,52 @@
+use std::{fmt::Display, path::PathBuf};
+
+/// The season the flat `dayNN` names of the command line refer to when no year is given.
+pub const LATEST_YEAR: u16 = 2022;
+
+/// A day of a season, the crates of which live in `yYYYY/dayNN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    pub fn new(year: u16, day: u8) -> Self {
+        Self { year, day }
+    }
+
+    /// `y2022`, the directory of the season and its table in the configuration file.
+    pub fn season(&self) -> String {
+        format!("y{}", self.year)
+    }
+
+    /// `day01`, the directory of the day within its season and its table within the season's.
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    /// `y2022/day01`, relative to the workspace root.
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(self.season()).join(self.name())
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        let puzzle = Puzzle::new(2022, 7);
+
+        assert_eq!(puzzle.dir(), PathBuf::from("y2022/day07"));
+        assert_eq!(puzzle.to_string(), "2022 day 7");
+        assert!(puzzle < Puzzle::new(2023, 1));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt::Display, fs, path::PathBuf};
+
+use anyhow::Context as _;
+
+use crate::{
+    journal_path, Args, Budget, Config, Diagnostics, Explain, Journal, Mode, Puzzle,
+    DEFAULT_EXPLAIN_LIMIT,
+};
+
+pub const DEFAULT_INPUT: &str = "input.txt";
+
+/// Everything a solver needs besides its input.
+#[derive(Default)]
+pub struct Context {
+    pub config: Config,
+    pub explain: Explain,
+    pub budget: Budget,
+    pub diagnostics: Diagnostics,
+}
+
+impl Context {
+    pub fn new(puzzle: Puzzle, args: &Args) -> anyhow::Result<Self> {
+        Ok(Self {
+            config: Config::load(puzzle, args)?,
+            explain: Explain::new(args),
+            budget: Budget::new(args.time_limit, args.memory_limit),
+            diagnostics: Diagnostics::new(match args.lenient {
+                true => Mode::Lenient,
+                false => Mode::Strict,
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Answers {
+    pub fn new(part1: impl Display, part2: impl Display) -> Self {
+        Self {
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> &str {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "part 1: {}", self.part1)?;
+        write!(f, "part 2: {}", self.part2)
+    }
+}
+
+pub type Solver = fn(&Context, &str) -> anyhow::Result<Answers>;
+
+/// The name of the only implementation of a day that has just the one.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// One of several ways to solve a day, picked with `--impl <name>`.
+///
+/// A day keeping a readable and a fast version side by side lists both, the first one being the default.
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub name: &'static str,
+    pub solve: Solver,
+}
+
+impl Implementation {
+    pub const fn new(name: &'static str, solve: Solver) -> Self {
+        Self { name, solve }
+    }
+}
+
+/// The implementation called `name`, or the first one if there's no name.
+pub fn select(
+    implementations: &[Implementation],
+    name: Option<&str>,
+) -> anyhow::Result<Implementation> {
+    let Some(name) = name else {
+        return implementations
+            .first()
+            .copied()
+            .context("no implementations to pick from");
+    };
+
+    implementations
+        .iter()
+        .find(|implementation| implementation.name == name)
+        .copied()
+        .with_context(|| {
+            let names = implementations
+                .iter()
+                .map(|implementation| implementation.name)
+                .collect::<Vec<_>>();
+
+            format!(
+                "unknown --impl {name:?}, expected one of {}",
+                names.join(", ")
+            )
+        })
+}
+
+/// Runs a day with the command line arguments and prints its answers,
+/// preceded by a summary of the records skipped in lenient mode
+/// and warnings about answers the journal already knows to be wrong.
+pub fn run(year: u16, day: u8, solve: Solver) -> anyhow::Result<()> {
+    run_implementations(
+        year,
+        day,
+        &[Implementation::new(DEFAULT_IMPLEMENTATION, solve)],
+    )
+}
+
+/// Runs the implementation of a day picked with `--impl`, like `run` does for days with only one.
+pub fn run_implementations(
+    year: u16,
+    day: u8,
+    implementations: &[Implementation],
+) -> anyhow::Result<()> {
+    let args = Args::from_env()?;
+    let implementation = select(implementations, args.implementation.as_deref())?;
+    let context = Context::new(Puzzle::new(year, day), &args)?;
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT));
+    let input = fs::read_to_string(&path).context(format!("failed to read {}", path.display()))?;
+
+    let answers = (implementation.solve)(&context, &input);
+
+    // the skipped records are worth knowing about even when the solver failed later on
+    let limit = args.explain_limit.unwrap_or(DEFAULT_EXPLAIN_LIMIT);
+    if let Some(summary) = context.diagnostics.summary(limit) {
+        eprintln!("{summary}");
+    }
+
+    let answers = answers?;
+    let path = journal_path(&path, args.journal.as_deref());
+    let mut journal = Journal::load(&path)?;
+
+    for warning in journal.check_answers(&answers) {
+        eprintln!("warning: {warning}");
+    }
+
+    println!("{answers}");
+
+    if !args.feedback.is_empty() {
+        for &(part, feedback) in &args.feedback {
+            journal.record(part, answers.part(part), feedback);
+        }
+        journal.save(&path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const IMPLEMENTATIONS: &[Implementation] = &[
+        Implementation::new("readable", |_, input| Ok(Answers::new(input.len(), 1))),
+        Implementation::new("fast", |_, input| Ok(Answers::new(input.len(), 2))),
+    ];
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select(IMPLEMENTATIONS, None).unwrap().name, "readable");
+        assert_eq!(select(IMPLEMENTATIONS, Some("fast")).unwrap().name, "fast");
+
+        let error = select(IMPLEMENTATIONS, Some("slow")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"unknown --impl "slow", expected one of readable, fast"#
+        );
+    }
+}
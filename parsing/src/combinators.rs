@@ -0,0 +1,90 @@
+use crate::{Cursor, ParseResult};
+
+/// Parses one or more items separated by `separator`.
+pub fn separated<'a, T>(
+    cursor: &mut Cursor<'a>,
+    separator: &str,
+    mut item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut items = vec![item(cursor)?];
+
+    while cursor.rest().starts_with(separator) {
+        cursor.literal(separator)?;
+        items.push(item(cursor)?);
+    }
+
+    Ok(items)
+}
+
+/// Parses fixed-width columns separated by `separator`.
+///
+/// Each item only sees the characters of its own column and must consume all of them.
+/// A trailing separator at the end of the line is accepted.
+pub fn columns<'a, T>(
+    cursor: &mut Cursor<'a>,
+    width: usize,
+    separator: &str,
+    mut item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut items = Vec::new();
+
+    while !cursor.is_empty() {
+        let mut column = cursor.take(width)?;
+        items.push(item(&mut column)?);
+        column.end()?;
+
+        if cursor.is_empty() {
+            break;
+        }
+
+        cursor.literal(separator)?;
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separated() {
+        let mut cursor = Cursor::new("2-4,6-8");
+
+        let values = separated(&mut cursor, ",", |c| {
+            let start = c.integer::<u32>()?;
+            c.literal("-")?;
+            Ok((start, c.integer::<u32>()?))
+        })
+        .unwrap();
+
+        assert_eq!(values, vec![(2, 4), (6, 8)]);
+        cursor.end().unwrap();
+    }
+
+    #[test]
+    fn test_columns() {
+        let cell = |c: &mut Cursor| match c.rest() {
+            "   " => {
+                c.take(3)?;
+                Ok(None)
+            }
+            _ => {
+                c.literal("[")?;
+                let value = c.char()?;
+                c.literal("]")?;
+                Ok(Some(value))
+            }
+        };
+
+        let mut cursor = Cursor::new("    [D] ");
+        assert_eq!(
+            columns(&mut cursor, 3, " ", cell).unwrap(),
+            vec![None, Some('D')]
+        );
+
+        let mut cursor = Cursor::new("[A] {B}");
+        let error = columns(&mut cursor, 3, " ", cell).unwrap_err();
+        assert_eq!(error.column(), 5);
+    }
+}
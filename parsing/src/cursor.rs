@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+use crate::{ParseError, ParseResult};
+
+/// how much of the remaining input is quoted in error messages
+const FOUND_PREVIEW: usize = 16;
+
+/// A position inside a single line of input.
+///
+/// Sub-cursors created by [`Cursor::take`] keep pointing into the original line,
+/// so errors raised inside a fixed-width column still report the right column.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    offset: usize,
+    limit: usize,
+    line: Option<usize>,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            offset: 0,
+            limit: input.len(),
+            line: None,
+        }
+    }
+
+    pub fn at_line(input: &'a str, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(input)
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The 1-based column of the next character.
+    pub fn column(&self) -> usize {
+        self.input[..self.offset].chars().count() + 1
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.offset..self.limit]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.limit
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Builds an error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let rest = self.rest();
+
+        let found = match rest.is_empty() {
+            true => "end of line".to_string(),
+            false => format!("{:?}", rest.chars().take(FOUND_PREVIEW).collect::<String>()),
+        };
+
+        self.error_found(expected, found)
+    }
+
+    fn error_found(&self, expected: impl Into<String>, found: impl Into<String>) -> ParseError {
+        let error = ParseError::new(self.column(), expected, found);
+
+        match self.line {
+            Some(line) => error.at_line(line),
+            None => error,
+        }
+    }
+
+    pub fn literal(&mut self, expected: &str) -> ParseResult<()> {
+        match self.rest().starts_with(expected) {
+            true => {
+                self.offset += expected.len();
+                Ok(())
+            }
+            false => Err(self.error(format!("{expected:?}"))),
+        }
+    }
+
+    pub fn char(&mut self) -> ParseResult<char> {
+        let c = self.peek().ok_or_else(|| self.error("a character"))?;
+        self.offset += c.len_utf8();
+
+        Ok(c)
+    }
+
+    pub fn digit(&mut self) -> ParseResult<u32> {
+        let digit = self
+            .peek()
+            .and_then(|c| c.to_digit(10))
+            .ok_or_else(|| self.error("a digit"))?;
+        self.offset += 1;
+
+        Ok(digit)
+    }
+
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(rest.len(), |(i, _)| i);
+
+        self.offset += len;
+
+        &rest[..len]
+    }
+
+    /// Consumes a run of non-whitespace characters.
+    pub fn word(&mut self) -> ParseResult<&'a str> {
+        let start = self.clone();
+        let word = self.take_while(|c| !c.is_whitespace());
+
+        match word.is_empty() {
+            true => Err(start.error("a word")),
+            false => Ok(word),
+        }
+    }
+
+    /// Consumes a word and maps it to a value, reporting the word's position if the mapping fails.
+    pub fn word_map<T>(
+        &mut self,
+        expected: &str,
+        map: impl FnOnce(&'a str) -> Option<T>,
+    ) -> ParseResult<T> {
+        let start = self.clone();
+        let word = self.word()?;
+
+        map(word).ok_or_else(|| start.error(expected))
+    }
+
+    /// Consumes an optionally signed decimal integer.
+    pub fn integer<T: FromStr>(&mut self) -> ParseResult<T> {
+        let start = self.clone();
+
+        let sign = match self.peek() {
+            Some('-') | Some('+') => 1,
+            _ => 0,
+        };
+        let digits = self.rest()[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len() - sign);
+
+        if digits == 0 {
+            return Err(start.error("an integer"));
+        }
+
+        let text = &self.rest()[..sign + digits];
+
+        let value = text
+            .parse::<T>()
+            .map_err(|_| start.error_found("an integer in range", format!("{text:?}")))?;
+        self.offset += text.len();
+
+        Ok(value)
+    }
+
+    /// Splits off the next `width` characters as a cursor of their own.
+    pub fn take(&mut self, width: usize) -> ParseResult<Cursor<'a>> {
+        let len = self
+            .rest()
+            .char_indices()
+            .nth(width)
+            .map_or(self.rest().len(), |(i, _)| i);
+
+        if self.rest()[..len].chars().count() < width {
+            return Err(self.error(format!("{width} characters")));
+        }
+
+        let column = Cursor {
+            limit: self.offset + len,
+            ..self.clone()
+        };
+        self.offset += len;
+
+        Ok(column)
+    }
+
+    pub fn end(&self) -> ParseResult<()> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(self.error("end of line")),
+        }
+    }
+
+    pub fn parse<T: Parse>(&mut self) -> ParseResult<T> {
+        T::parse(self)
+    }
+}
+
+/// Types that can be read from a [`Cursor`].
+pub trait Parse: Sized {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self>;
+}
+
+macro_rules! impl_parse_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Parse for $t {
+                fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+                    cursor.integer()
+                }
+            }
+        )*
+    };
+}
+
+impl_parse_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Parse for String {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        cursor.word().map(str::to_string)
+    }
+}
+
+/// Parses the whole of `input`, failing if anything is left over.
+pub fn parse_str<T: Parse>(input: &str) -> ParseResult<T> {
+    let mut cursor = Cursor::new(input);
+    let value = cursor.parse()?;
+    cursor.end()?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_and_integer() {
+        let mut cursor = Cursor::new("move 12 from -3");
+
+        cursor.literal("move ").unwrap();
+        assert_eq!(cursor.integer::<u32>().unwrap(), 12);
+        cursor.literal(" from ").unwrap();
+        assert_eq!(cursor.integer::<i32>().unwrap(), -3);
+        cursor.end().unwrap();
+    }
+
+    #[test]
+    fn test_error_position() {
+        let mut cursor = Cursor::at_line("move x from 3", 4);
+        cursor.literal("move ").unwrap();
+
+        let error = cursor.integer::<u32>().unwrap_err();
+        assert_eq!(error.line(), Some(4));
+        assert_eq!(error.column(), 6);
+        assert_eq!(
+            error.to_string(),
+            r#"line 4, column 6: expected an integer, found "x from 3""#
+        );
+
+        let error = Cursor::new("300").integer::<u8>().unwrap_err();
+        assert_eq!(error.expected(), "an integer in range");
+    }
+
+    #[test]
+    fn test_take_keeps_columns() {
+        let mut cursor = Cursor::new("[A] [x]");
+
+        let mut first = cursor.take(3).unwrap();
+        first.literal("[").unwrap();
+        assert_eq!(first.char().unwrap(), 'A');
+        first.literal("]").unwrap();
+        first.end().unwrap();
+
+        cursor.literal(" ").unwrap();
+
+        let mut second = cursor.take(3).unwrap();
+        second.literal("[").unwrap();
+        let error = second.digit().unwrap_err();
+        assert_eq!(error.column(), 6);
+
+        assert!(cursor.take(1).is_err());
+    }
+}
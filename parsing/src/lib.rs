@@ -0,0 +1,76 @@
+// lets the code generated by `#[derive(Parse)]` refer to `::parsing` from within this crate
+extern crate self as parsing;
+
+mod combinators;
+mod cursor;
+mod error;
+mod lines;
+
+pub use combinators::{columns, separated};
+pub use cursor::{parse_str, Cursor, Parse};
+pub use error::{ParseError, ParseResult};
+pub use lines::{blocks, lines, Block, Blocks, Line, Lines};
+pub use parsing_derive::Parse;
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Parse)]
+    #[aoc(format = "{start}-{end}")]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Parse)]
+    #[aoc(format = "{} => {}!")]
+    struct Rename(String, Range);
+
+    fn parse_doubled(cursor: &mut Cursor<'_>) -> ParseResult<u32> {
+        Ok(cursor.integer::<u32>()? * 2)
+    }
+
+    #[derive(Debug, PartialEq, Eq, Parse)]
+    #[aoc(format = "{{{}}}")]
+    struct Doubled {
+        #[aoc(with = "parse_doubled")]
+        value: u32,
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!("2-4".parse::<Range>().unwrap(), Range { start: 2, end: 4 });
+        assert_eq!(
+            "a => 1-3!".parse::<Rename>().unwrap(),
+            Rename("a".to_string(), Range { start: 1, end: 3 })
+        );
+        assert_eq!("{21}".parse::<Doubled>().unwrap(), Doubled { value: 42 });
+    }
+
+    #[test]
+    fn test_derive_errors() {
+        let error = "2-4x".parse::<Range>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"column 4: expected end of line, found "x""#
+        );
+
+        let error = "a -> 1-3!".parse::<Rename>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"column 2: expected " => ", found " -> 1-3!""#
+        );
+
+        let error = Line {
+            number: 7,
+            text: "a => 1-3".to_string(),
+        }
+        .parse::<Rename>()
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 7, column 9: expected \"!\", found end of line"
+        );
+    }
+}
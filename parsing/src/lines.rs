@@ -0,0 +1,147 @@
+use std::io::{self, BufRead};
+
+use crate::{Cursor, Parse, ParseResult};
+
+/// A single line of input and its 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    pub fn cursor(&self) -> Cursor<'_> {
+        Cursor::at_line(&self.text, self.number)
+    }
+
+    /// Parses the whole line, failing if anything is left over.
+    pub fn parse<T: Parse>(&self) -> ParseResult<T> {
+        let mut cursor = self.cursor();
+        let value = cursor.parse()?;
+        cursor.end()?;
+
+        Ok(value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+pub struct Lines<R> {
+    lines: io::Lines<R>,
+    number: usize,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.lines.next()?;
+        self.number += 1;
+
+        Some(text.map(|text| Line {
+            number: self.number,
+            text,
+        }))
+    }
+}
+
+/// Splits the input into numbered lines.
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        lines: reader.lines(),
+        number: 0,
+    }
+}
+
+/// Lines between blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub lines: Vec<Line>,
+}
+
+impl Block {
+    /// The line number of the first line in the block.
+    pub fn first_line(&self) -> usize {
+        self.lines.first().map_or(0, |line| line.number)
+    }
+}
+
+pub struct Blocks<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Iterator for Blocks<R> {
+    type Item = io::Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut lines = Vec::new();
+
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+
+            match line.is_empty() {
+                // several blank lines in a row don't produce empty blocks
+                true if lines.is_empty() => continue,
+                true => break,
+                false => lines.push(line),
+            }
+        }
+
+        match lines.is_empty() {
+            true => None,
+            false => Some(Ok(Block { lines })),
+        }
+    }
+}
+
+/// Splits the input into blocks of lines separated by blank lines.
+pub fn blocks<R: BufRead>(reader: R) -> Blocks<R> {
+    Blocks {
+        lines: lines(reader),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"1
+2
+
+3
+
+
+4
+"#;
+
+    #[test]
+    fn test_lines() {
+        let lines = lines(INPUT.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[3].number, 4);
+        assert_eq!(lines[3].parse::<u32>().unwrap(), 3);
+
+        let error = lines[2].parse::<u32>().unwrap_err();
+        assert_eq!(error.line(), Some(3));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks = blocks(INPUT.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].lines.len(), 2);
+        assert_eq!(blocks[1].first_line(), 4);
+        assert_eq!(blocks[2].first_line(), 7);
+    }
+}
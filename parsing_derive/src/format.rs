@@ -0,0 +1,106 @@
+/// A piece of a line template such as `move {} from {} to {}`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Segment {
+    Literal(String),
+    /// `{}` or `{name}`
+    Placeholder(Option<String>),
+}
+
+pub fn parse(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) if c == '_' || c.is_alphanumeric() => name.push(c),
+                        Some(c) => return Err(format!("unexpected {c:?} in placeholder")),
+                        None => return Err("unterminated placeholder".to_string()),
+                    }
+                }
+
+                match literal.is_empty() {
+                    true if matches!(segments.last(), Some(Segment::Placeholder(_))) => {
+                        // without a literal in between we wouldn't know where the first value ends
+                        return Err("placeholders must be separated by a literal".to_string());
+                    }
+                    true => {}
+                    false => segments.push(Segment::Literal(std::mem::take(&mut literal))),
+                }
+
+                segments.push(Segment::Placeholder((!name.is_empty()).then_some(name)));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".to_string()),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    let positional = segments
+        .iter()
+        .any(|s| matches!(s, Segment::Placeholder(None)));
+    let named = segments
+        .iter()
+        .any(|s| matches!(s, Segment::Placeholder(Some(_))));
+
+    if positional && named {
+        return Err("cannot mix `{}` and `{name}` placeholders".to_string());
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("move {} from {} to {}").unwrap(),
+            vec![
+                Segment::Literal("move ".to_string()),
+                Segment::Placeholder(None),
+                Segment::Literal(" from ".to_string()),
+                Segment::Placeholder(None),
+                Segment::Literal(" to ".to_string()),
+                Segment::Placeholder(None),
+            ]
+        );
+
+        assert_eq!(
+            parse("{{{start}}}").unwrap(),
+            vec![
+                Segment::Literal("{".to_string()),
+                Segment::Placeholder(Some("start".to_string())),
+                Segment::Literal("}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("{}{}").is_err());
+        assert!(parse("{} {name}").is_err());
+        assert!(parse("{start").is_err());
+        assert!(parse("end}").is_err());
+        assert!(parse("{a-b}").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+mod format;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Member, Path, Type};
+
+use format::Segment;
+
+struct Field {
+    member: Member,
+    ty: Type,
+    with: Option<Path>,
+}
+
+/// Derives `parsing::Parse` and `FromStr` from a line template.
+///
+/// ```ignore
+/// #[derive(Parse)]
+/// #[aoc(format = "move {} from {} to {}")]
+/// struct Move {
+///     amount: usize,
+///     #[aoc(with = "parse_stack_index")]
+///     from: usize,
+///     #[aoc(with = "parse_stack_index")]
+///     to: usize,
+/// }
+/// ```
+///
+/// `{}` placeholders are filled with the fields in declaration order, `{name}` placeholders by name.
+/// Every field is parsed with its own `Parse` implementation unless `with` names a function
+/// taking a `&mut Cursor` instead.
+#[proc_macro_derive(Parse, attributes(aoc))]
+pub fn derive_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let template = parse_format(&input)?;
+    let segments = format::parse(&template.value()).map_err(|e| Error::new(template.span(), e))?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`Parse` can only be derived for structs",
+        ));
+    };
+
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            Ok(Field {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                },
+                ty: field.ty.clone(),
+                with: parse_with(field)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut steps = Vec::new();
+    let mut used = vec![false; fields.len()];
+    let mut next_positional = 0;
+
+    for segment in segments {
+        let index = match segment {
+            Segment::Literal(text) => {
+                steps.push(quote! { cursor.literal(#text)?; });
+                continue;
+            }
+            Segment::Placeholder(None) => {
+                next_positional += 1;
+                next_positional - 1
+            }
+            Segment::Placeholder(Some(name)) => fields
+                .iter()
+                .position(|f| matches!(&f.member, Member::Named(ident) if ident == &name))
+                .ok_or_else(|| Error::new(template.span(), format!("no field named `{name}`")))?,
+        };
+
+        let field = fields.get(index).ok_or_else(|| {
+            Error::new(
+                template.span(),
+                format!(
+                    "the format has more placeholders than the {} fields",
+                    fields.len()
+                ),
+            )
+        })?;
+
+        if std::mem::replace(&mut used[index], true) {
+            return Err(Error::new(
+                template.span(),
+                "each field can only appear once in the format",
+            ));
+        }
+
+        let variable = format_ident!("field_{}", index);
+        let ty = &field.ty;
+        let value = match &field.with {
+            Some(with) => quote! { #with(cursor)? },
+            None => quote! { <#ty as ::parsing::Parse>::parse(cursor)? },
+        };
+
+        steps.push(quote! { let #variable: #ty = #value; });
+    }
+
+    if let Some(index) = used.iter().position(|used| !used) {
+        let field = &fields[index];
+        return Err(Error::new_spanned(
+            &field.member,
+            "the field is missing from the format",
+        ));
+    }
+
+    let variables = (0..fields.len()).map(|i| format_ident!("field_{}", i));
+    let members = fields.iter().map(|f| &f.member);
+    let construct = match data.fields {
+        Fields::Unit => quote! { Self },
+        _ => quote! { Self { #(#members: #variables),* } },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::parsing::Parse for #name #ty_generics #where_clause {
+            fn parse(cursor: &mut ::parsing::Cursor<'_>) -> ::parsing::ParseResult<Self> {
+                #(#steps)*
+
+                Ok(#construct)
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::parsing::ParseError;
+
+            fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                ::parsing::parse_str(value)
+            }
+        }
+    })
+}
+
+fn parse_format(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut format = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| match meta.path.is_ident("format") {
+            true => {
+                format = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            }
+            false => Err(meta.error("expected `format`")),
+        })?;
+    }
+
+    format.ok_or_else(|| {
+        Error::new_spanned(&input.ident, "missing `#[aoc(format = \"...\")]` attribute")
+    })
+}
+
+fn parse_with(field: &syn::Field) -> syn::Result<Option<Path>> {
+    let mut with = None;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| match meta.path.is_ident("with") {
+            true => {
+                with = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                Ok(())
+            }
+            false => Err(meta.error("expected `with`")),
+        })?;
+    }
+
+    Ok(with)
+}
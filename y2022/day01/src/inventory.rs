@@ -0,0 +1,143 @@
+use std::ops::RangeBounds;
+
+use crate::parser::Elf;
+
+/// What elves are compared by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// the calories of all items together
+    Calories,
+    /// the number of items
+    Items,
+    /// the calories of the heaviest item
+    HeaviestItem,
+}
+
+impl Metric {
+    pub fn of(self, elf: &Elf) -> u128 {
+        match self {
+            Self::Calories => elf.total,
+            Self::Items => elf.items.len() as u128,
+            Self::HeaviestItem => elf.items.iter().max().copied().unwrap_or_default() as u128,
+        }
+    }
+}
+
+/// Every elf of an input in input order, for questions the top elves alone can't answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the next elf, which has to be numbered right after the last one.
+    pub(crate) fn push(&mut self, elf: Elf) {
+        debug_assert_eq!(elf.index, self.elves.len() + 1);
+        self.elves.push(elf);
+    }
+
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The elf numbered `index`, counting from 1 as in the input.
+    pub fn get(&self, index: usize) -> Option<&Elf> {
+        index.checked_sub(1).and_then(|i| self.elves.get(i))
+    }
+
+    /// The elves whose metric lies in `range`, in input order.
+    pub fn between(
+        &self,
+        metric: Metric,
+        range: impl RangeBounds<u128>,
+    ) -> impl Iterator<Item = &Elf> {
+        self.elves
+            .iter()
+            .filter(move |elf| range.contains(&metric.of(elf)))
+    }
+
+    /// All elves by their metric, the largest first and elves with the same value in input order.
+    pub fn sorted_by(&self, metric: Metric) -> Vec<&Elf> {
+        let mut elves = self.elves.iter().collect::<Vec<_>>();
+        elves.sort_by_key(|elf| std::cmp::Reverse(metric.of(elf)));
+        elves
+    }
+
+    /// The first of the elves with the largest metric.
+    pub fn max_by(&self, metric: Metric) -> Option<&Elf> {
+        self.sorted_by(metric).first().copied()
+    }
+
+    /// The calories of the `k` heaviest elves together, or of all of them when there are fewer.
+    pub fn top_sum(&self, k: usize) -> u128 {
+        self.sorted_by(Metric::Calories)
+            .into_iter()
+            .take(k)
+            .map(|elf| elf.total)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::parser::{tests::read_example, Parser};
+
+    fn indices<'a>(elves: impl IntoIterator<Item = &'a Elf>) -> Vec<usize> {
+        elves.into_iter().map(|elf| elf.index).collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let parser = read_example(Parser::new(1).with_inventory());
+        let inventory = parser.inventory().unwrap();
+
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.get(2).unwrap().items, vec![4000]);
+        assert_eq!(inventory.get(0), None);
+        assert_eq!(inventory.get(6), None);
+
+        assert_eq!(
+            indices(inventory.between(Metric::Calories, 10000..=20000)),
+            vec![3, 5]
+        );
+        assert_eq!(indices(inventory.between(Metric::Items, 3..)), vec![1, 4]);
+        assert_eq!(inventory.max_by(Metric::HeaviestItem).unwrap().index, 5);
+
+        // elves with as many items stay in input order
+        assert_eq!(
+            indices(inventory.sorted_by(Metric::Items)),
+            vec![1, 4, 3, 2, 5]
+        );
+    }
+
+    #[test]
+    fn test_same_as_parser() {
+        let parser = read_example(Parser::new(3).with_inventory().with_totals());
+        let inventory = parser.inventory().unwrap();
+
+        for k in 0..=3 {
+            assert_eq!(Some(inventory.top_sum(k)), parser.top_sum(k));
+        }
+        assert_eq!(
+            inventory.sorted_by(Metric::Calories)[..3],
+            parser.ranked_elves()[..]
+        );
+        assert_eq!(
+            Some(inventory.between(Metric::Calories, 10001..).count()),
+            parser.count_exceeding(10000)
+        );
+    }
+}
@@ -0,0 +1,121 @@
+pub mod inventory;
+pub mod parallel;
+pub mod parser;
+pub mod partition;
+pub mod ranking;
+pub mod statistics;
+
+use std::thread;
+
+use common::{Answers, Config, Context, Implementation};
+
+use parser::{Parser, DEFAULT_NAME_PREFIX};
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::new("sequential", solve),
+    Implementation::new("parallel", |context, input| {
+        solve_with(context, input, Reading::Parallel)
+    }),
+];
+
+/// How the input is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    /// one line after the other
+    Sequential,
+    /// in chunks of whole elves on several threads
+    Parallel,
+}
+
+/// A parser set up by the configuration, with `wide` totals and the `name_prefix` of name lines.
+pub fn parser(config: &Config, top_n: usize) -> anyhow::Result<Parser> {
+    let name_prefix = config.get(1, "name_prefix", DEFAULT_NAME_PREFIX.to_string())?;
+
+    Ok(Parser::new(top_n)
+        .with_wide(config.get(1, "wide", false)?)
+        .with_name_prefix(&name_prefix))
+}
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    solve_with(context, input, Reading::Sequential)
+}
+
+pub fn solve_with(context: &Context, input: &str, reading: Reading) -> anyhow::Result<Answers> {
+    let config = &context.config;
+    let explain = &context.explain;
+
+    let parts = [config.get(1, "top_n", 1)?, config.get(2, "top_n", 3)?];
+
+    // the parser checks that as many elves as the larger part takes add up within the limit
+    let top_n = parts.into_iter().max().unwrap_or_default();
+    // only the top elves are kept, however many there are
+    let parser = parser(config, top_n)?;
+
+    let parser = match reading {
+        Reading::Sequential => {
+            let mut parser = parser;
+
+            for (i, line) in input.lines().enumerate() {
+                context.budget.check(|| format!("line {}", i + 1))?;
+                context.diagnostics.recover(parser.parse(line))?;
+            }
+
+            parser.finish()?;
+            parser
+        }
+        Reading::Parallel => {
+            // 0 for as many threads as the machine runs at once
+            let threads = match config.get(1, "threads", 0)? {
+                0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+                threads => threads,
+            };
+
+            parallel::parse(
+                input,
+                parser,
+                threads,
+                &context.budget,
+                &context.diagnostics,
+            )?
+        }
+    };
+
+    let ranked = parser.ranked_elves();
+
+    for (part, top_n) in (1..).zip(parts) {
+        let mut trace = explain.list(|| format!("part {part}: elves carrying the most calories"));
+
+        for elf in ranked.iter().take(top_n) {
+            trace.entry(|| match elf.items.is_empty() {
+                true => format!("{} carries nothing", elf.name()),
+                false => format!(
+                    "{} carries {} calories in {} items on lines {}-{}",
+                    elf.name(),
+                    elf.total,
+                    elf.items.len(),
+                    elf.lines.start,
+                    elf.lines.end - 1
+                ),
+            });
+        }
+    }
+
+    // both parts are within the `top_n` of the parser
+    let [part1, part2] = parts.map(|top_n| parser.top_sum(top_n).unwrap_or_default());
+
+    Ok(Answers::new(part1, part2))
+}
+
+/// Runs the parser over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    let mut parser = parser(&context.config, 1)?;
+
+    for (i, line) in input.lines().enumerate() {
+        context.budget.check(|| format!("line {}", i + 1))?;
+        context.diagnostics.recover(parser.parse(line))?;
+    }
+
+    parser.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/1
+fn main() -> anyhow::Result<()> {
+    common::run_implementations(2022, 1, y2022_day01::IMPLEMENTATIONS)
+}
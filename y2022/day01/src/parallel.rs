@@ -0,0 +1,237 @@
+use std::thread;
+
+use common::{Budget, Diagnostics};
+use parsing::ParseError;
+
+use crate::parser::{Overflow, Parser};
+
+/// Parses an inventory on `threads` threads, each reading a run of whole elves with a parser set up like
+/// `parser`, and merges them into what `parser` gives reading the input line by line.
+///
+/// Bad lines are skipped or reported exactly as they would be there, with their line in the whole input.
+pub fn parse(
+    input: &str,
+    mut parser: Parser,
+    threads: usize,
+    budget: &Budget,
+    diagnostics: &Diagnostics,
+) -> anyhow::Result<Parser> {
+    let mode = diagnostics.mode();
+
+    let results = thread::scope(|scope| {
+        let parser = &parser;
+        let workers = chunks(input, threads)
+            .into_iter()
+            .enumerate()
+            .map(|(chunk, text)| {
+                scope.spawn(move || -> anyhow::Result<_> {
+                    let diagnostics = Diagnostics::new(mode);
+                    let mut parser = parser.empty();
+
+                    for (i, line) in text.lines().enumerate() {
+                        budget.check(|| format!("chunk {}, line {}", chunk + 1, i + 1))?;
+                        diagnostics.recover(parser.parse(line))?;
+                    }
+                    parser.flush();
+
+                    Ok((parser, diagnostics.warnings()))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("a worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    // in input order, so that the first bad line of the input is the one that stops a strict run
+    for result in results {
+        let (chunk, warnings) = result.map_err(|error| shifted(error, &parser))?;
+
+        for warning in warnings {
+            diagnostics.recover(Err::<(), _>(warning.shifted(parser.line())))?;
+        }
+        parser.append(chunk);
+    }
+
+    parser.finish()?;
+
+    Ok(parser)
+}
+
+/// Moves an error of a chunk to where the chunk starts, `parser` having read everything before it.
+fn shifted(error: anyhow::Error, parser: &Parser) -> anyhow::Error {
+    let error = match error.downcast::<ParseError>() {
+        Ok(error) => return error.shifted(parser.line()).into(),
+        Err(error) => error,
+    };
+
+    match error.downcast::<Overflow>() {
+        Ok(overflow) => overflow.shifted(parser.elves(), parser.line()).into(),
+        Err(error) => error,
+    }
+}
+
+/// Splits the input into about `count` parts of the same size, each but the last ending with a blank line
+/// so that no elf is split between two of them.
+fn chunks(input: &str, count: usize) -> Vec<&str> {
+    let size = input.len() / count.max(1) + 1;
+    let mut chunks = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let end = rest.as_bytes()[size.min(rest.len()) - 1..]
+            .windows(2)
+            .position(|window| window == b"\n\n")
+            .map_or(rest.len(), |position| size + position + 1);
+
+        let (chunk, after) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = after;
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::statistics::Statistics;
+
+    /// Elves of one to four items, some of them named, with the occasional elf carrying as much as an earlier one.
+    fn inventory(elves: usize) -> String {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |limit: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % limit
+        };
+
+        let mut input = String::new();
+        for elf in 0..elves {
+            if elf > 0 {
+                input.push('\n');
+            }
+            if next(3) == 0 {
+                input.push_str(&format!("# Elf {elf}\n"));
+            }
+            for _ in 0..=next(4) {
+                input.push_str(&format!("{}\n", 1000 * (1 + next(60))));
+            }
+        }
+        input
+    }
+
+    fn sequential(input: &str, top_n: usize, diagnostics: &Diagnostics) -> anyhow::Result<Parser> {
+        Parser::read(BufReader::new(input.as_bytes()), top_n, diagnostics)
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = "1000\n2000\n\n3000\n\n4000\n";
+
+        assert_eq!(chunks(input, 3), vec!["1000\n2000\n\n", "3000\n\n4000\n"]);
+        assert_eq!(
+            chunks(input, 5),
+            vec!["1000\n2000\n\n", "3000\n\n", "4000\n"]
+        );
+        assert_eq!(chunks(input, 1), vec![input]);
+        assert_eq!(chunks(input, 100).concat(), input);
+        assert_eq!(chunks("", 4), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_same_as_sequential() {
+        for elves in [1, 2, 10, 1000] {
+            let input = inventory(elves);
+            let expected = Parser::new(3)
+                .with_inventory()
+                .with_totals()
+                .with_statistics(Statistics::streaming(0.01))
+                .read_from(BufReader::new(input.as_bytes()), &Diagnostics::strict())
+                .unwrap();
+
+            for threads in [1, 2, 3, 8, 64] {
+                let parser = parse(
+                    &input,
+                    Parser::new(3)
+                        .with_inventory()
+                        .with_totals()
+                        .with_statistics(Statistics::streaming(0.01)),
+                    threads,
+                    &Budget::unlimited(),
+                    &Diagnostics::strict(),
+                )
+                .unwrap();
+
+                assert_eq!(
+                    parser.ranked_elves(),
+                    expected.ranked_elves(),
+                    "{elves} elves"
+                );
+                assert_eq!(
+                    parser.count_exceeding(30000),
+                    expected.count_exceeding(30000)
+                );
+                assert_eq!(parser.line(), expected.line());
+                assert_eq!(parser.inventory(), expected.inventory());
+                assert_eq!(
+                    parser.statistics().unwrap().report(&[50.0], 5),
+                    expected.statistics().unwrap().report(&[50.0], 5)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = format!("{}\n{}\n1\n", inventory(100), u64::MAX);
+        let overflow = |error: anyhow::Error| error.downcast::<Overflow>().unwrap();
+
+        let expected = sequential(&input, 3, &Diagnostics::strict()).err().unwrap();
+        let error = parse(
+            &input,
+            Parser::new(3),
+            8,
+            &Budget::unlimited(),
+            &Diagnostics::strict(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(overflow(error), overflow(expected));
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let input = inventory(200)
+            .replacen("\n\n", "\n\n\n", 3)
+            .replacen("000\n", "0x0\n", 5);
+        let line = |error: anyhow::Error| error.downcast::<ParseError>().unwrap().line();
+
+        let expected = sequential(&input, 3, &Diagnostics::strict()).err().unwrap();
+        let error = parse(
+            &input,
+            Parser::new(3),
+            8,
+            &Budget::unlimited(),
+            &Diagnostics::strict(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(line(error), line(expected));
+
+        let expected_warnings = Diagnostics::lenient();
+        let expected = sequential(&input, 3, &expected_warnings).unwrap();
+        let warnings = Diagnostics::lenient();
+        let parser = parse(&input, Parser::new(3), 8, &Budget::unlimited(), &warnings).unwrap();
+
+        assert_eq!(parser.ranked_elves(), expected.ranked_elves());
+        assert_eq!(warnings.warnings(), expected_warnings.warnings());
+        // an elf whose only item is skipped also leaves its blank line behind
+        assert!(warnings.warnings().len() >= 8);
+    }
+}
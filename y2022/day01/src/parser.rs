@@ -0,0 +1,811 @@
+use std::{
+    borrow::Cow,
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    error::Error,
+    fmt::Display,
+    io::{BufReader, Read},
+    ops::Range,
+};
+
+use common::Diagnostics;
+use parsing::{Cursor, ParseError};
+
+use crate::{inventory::Inventory, statistics::Statistics};
+
+/// The block of an elf in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// elves are numbered from 1 in input order
+    pub index: usize,
+    /// from the line starting with the name prefix before the items, if the block has one
+    pub name: Option<String>,
+    /// the input lines of the items, empty for an elf without any
+    pub lines: Range<usize>,
+    pub items: Vec<u64>,
+    /// wider than the items, for the totals of wide parsers
+    pub total: u128,
+}
+
+impl Elf {
+    fn new(index: usize, line: usize) -> Self {
+        Self {
+            index,
+            name: None,
+            lines: line..line,
+            items: Vec::new(),
+            total: 0,
+        }
+    }
+
+    /// The same elf, numbered as if `elves` elves and `lines` lines had been read before it.
+    pub(crate) fn shifted(mut self, elves: usize, lines: usize) -> Self {
+        self.index += elves;
+        self.lines = self.lines.start + lines..self.lines.end + lines;
+        self
+    }
+
+    /// The name of the elf, `3rd elf` for one without a name line.
+    pub fn name(&self) -> Cow<'_, str> {
+        match &self.name {
+            Some(name) => Cow::Borrowed(name),
+            None => Cow::Owned(format!("{} elf", ordinal(self.index))),
+        }
+    }
+}
+
+/// `1st`, `2nd`, `3rd`, `4th`, ... `11th`, `12th`, `13th`, ... `21st`.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{n}{suffix}")
+}
+
+/// What the name line of a block starts with, unless the parser is told otherwise.
+pub const DEFAULT_NAME_PREFIX: &str = "#";
+
+/// Calories adding up to more than the parser holds, `u64::MAX` unless it's wide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Overflow {
+    /// the items of an elf, at the line of the item that tips it over
+    Elf {
+        index: usize,
+        line: usize,
+        limit: u128,
+    },
+    /// the calories of the top elves together
+    Top { count: usize, limit: u128 },
+}
+
+impl Overflow {
+    /// Moves an overflow found in a part of the input parsed on its own to where that part starts.
+    pub(crate) fn shifted(self, elves: usize, lines: usize) -> Self {
+        match self {
+            Self::Elf { index, line, limit } => Self::Elf {
+                index: index + elves,
+                line: line + lines,
+                limit,
+            },
+            top => top,
+        }
+    }
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = match self {
+            Self::Elf { index, line, limit } => {
+                write!(
+                    f,
+                    "line {line}: elf {index} carries more than {limit} calories"
+                )?;
+                limit
+            }
+            Self::Top { count, limit } => {
+                write!(
+                    f,
+                    "the top {count} elves carry more than {limit} calories together"
+                )?;
+                limit
+            }
+        };
+
+        match *limit == NARROW_LIMIT {
+            true => write!(f, ", set `wide = true` for wider totals"),
+            false => Ok(()),
+        }
+    }
+}
+
+impl Error for Overflow {}
+
+/// The largest total of a parser that isn't wide.
+const NARROW_LIMIT: u128 = u64::MAX as u128;
+
+/// Elves ordered by calories, an earlier elf ranking above a later one carrying as many.
+struct Ranked(Elf);
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.total, Reverse(self.0.index)).cmp(&(other.0.total, Reverse(other.0.index)))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+/// Reads the elves one line at a time, keeping the `top_n` heaviest of them and the calories of all of them.
+///
+/// After a single pass it answers the top-k queries for any k up to `top_n`.
+pub struct Parser {
+    top_n: usize,
+    /// the largest total of an elf and of the top elves together
+    limit: u128,
+    /// the best elves so far, with the lowest ranked of them on top
+    maximums: BinaryHeap<Reverse<Ranked>>,
+    /// the calories of every elf if asked for, sorted once the input is finished
+    totals: Option<Vec<u128>>,
+    current: Elf,
+    line: usize,
+    statistics: Option<Statistics>,
+    inventory: Option<Inventory>,
+    /// `None` when blocks have no name lines
+    name_prefix: Option<String>,
+}
+
+impl Parser {
+    pub fn new(top_n: usize) -> Self {
+        Self {
+            top_n,
+            limit: NARROW_LIMIT,
+            maximums: BinaryHeap::with_capacity(top_n),
+            totals: None,
+            current: Elf::new(1, 1),
+            line: 0,
+            statistics: None,
+            inventory: None,
+            name_prefix: Some(DEFAULT_NAME_PREFIX.to_string()),
+        }
+    }
+
+    /// Takes lines starting with `prefix` before the items of a block as the name of the elf,
+    /// an empty prefix meaning blocks have no names.
+    pub fn with_name_prefix(mut self, prefix: &str) -> Self {
+        self.name_prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+        self
+    }
+
+    /// Lets the totals grow up to `u128::MAX` rather than `u64::MAX`, for made-up inputs.
+    pub fn with_wide(mut self, wide: bool) -> Self {
+        self.limit = match wide {
+            true => u128::MAX,
+            false => NARROW_LIMIT,
+        };
+        self
+    }
+
+    /// The most calories an elf or the top elves together may carry.
+    pub fn limit(&self) -> u128 {
+        self.limit
+    }
+
+    /// A parser with the same settings that hasn't read anything.
+    pub(crate) fn empty(&self) -> Self {
+        let mut parser = Self::new(self.top_n).with_wide(self.limit != NARROW_LIMIT);
+        parser.name_prefix = self.name_prefix.clone();
+        parser.statistics = self.statistics.as_ref().map(Statistics::empty);
+        parser.totals = self.totals.as_ref().map(|_| Vec::new());
+
+        match self.inventory.is_some() {
+            true => parser.with_inventory(),
+            false => parser,
+        }
+    }
+
+    /// Also keeps the total of every elf, for `count_exceeding`.
+    pub fn with_totals(mut self) -> Self {
+        self.totals = Some(Vec::new());
+        self
+    }
+
+    /// Also keeps every elf with its items, to be queried once the input is read.
+    pub fn with_inventory(mut self) -> Self {
+        self.inventory = Some(Inventory::new());
+        self
+    }
+
+    pub fn inventory(&self) -> Option<&Inventory> {
+        self.inventory.as_ref()
+    }
+
+    /// Also keeps statistics of every elf, not only of the top ones.
+    pub fn with_statistics(mut self, statistics: Statistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
+
+    pub fn statistics(&self) -> Option<&Statistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Reads a whole inventory, including the last elf when there's no blank line after it.
+    pub fn read<R: Read>(
+        buffer: BufReader<R>,
+        top_n: usize,
+        diagnostics: &Diagnostics,
+    ) -> anyhow::Result<Self> {
+        Self::new(top_n).read_from(buffer, diagnostics)
+    }
+
+    /// Feeds a whole inventory to the parser one line at a time, so it never has to fit in memory.
+    pub fn read_from<R: Read>(
+        mut self,
+        buffer: BufReader<R>,
+        diagnostics: &Diagnostics,
+    ) -> anyhow::Result<Self> {
+        for line in parsing::lines(buffer) {
+            diagnostics.recover(self.parse(&line?.text))?;
+        }
+        self.finish()?;
+
+        Ok(self)
+    }
+
+    /// Blank lines separate the elves, so one at the start or right after another is an error.
+    pub fn parse(&mut self, value: &str) -> anyhow::Result<()> {
+        self.line += 1;
+
+        match value.is_empty() {
+            true if self.current.items.is_empty() => {
+                return Err(ParseError::new(1, "calories", "a blank line")
+                    .at_line(self.line)
+                    .into());
+            }
+            true => self.finish_elf(),
+            false => match self.name(value) {
+                // a name only comes first, and only once
+                Some(_) if !self.current.items.is_empty() || self.current.name.is_some() => {
+                    return Err(ParseError::new(1, "calories", "a name")
+                        .at_line(self.line)
+                        .into());
+                }
+                Some(name) => {
+                    self.current.name = (!name.is_empty()).then(|| name.to_string());
+                }
+                None => self.parse_item(value)?,
+            },
+        }
+
+        Ok(())
+    }
+
+    /// The name on a name line, without the prefix.
+    fn name<'a>(&self, value: &'a str) -> Option<&'a str> {
+        let prefix = self.name_prefix.as_deref()?;
+
+        value.strip_prefix(prefix).map(str::trim)
+    }
+
+    /// An item line, adding to the calories of the elf.
+    fn parse_item(&mut self, value: &str) -> anyhow::Result<()> {
+        let mut cursor = Cursor::at_line(value, self.line);
+        let calories = cursor.integer::<u64>()?;
+        cursor.end()?;
+
+        let total = self
+            .current
+            .total
+            .checked_add(calories as u128)
+            .filter(|&total| total <= self.limit)
+            .ok_or(Overflow::Elf {
+                index: self.current.index,
+                line: self.line,
+                limit: self.limit,
+            })?;
+
+        if self.current.items.is_empty() {
+            self.current.lines.start = self.line;
+        }
+        self.current.lines.end = self.line + 1;
+        self.current.items.push(calories);
+        self.current.total = total;
+
+        Ok(())
+    }
+
+    /// Counts the last elf, which isn't followed by a blank line when the input doesn't end with one.
+    ///
+    /// Also checks that the top elves together stay within the limit, so that no top-k sum overflows.
+    pub fn finish(&mut self) -> anyhow::Result<()> {
+        self.flush();
+        if let Some(totals) = &mut self.totals {
+            totals.sort_unstable();
+        }
+
+        // a name line without items after it is as much of a block as nothing at all
+        if self.current.index == 1 || self.current.name.is_some() {
+            return Err(ParseError::new(1, "calories", "end of input")
+                .at_line(self.line + 1)
+                .into());
+        }
+
+        let top = self
+            .maximums
+            .iter()
+            .try_fold(0u128, |sum, Reverse(Ranked(elf))| {
+                sum.checked_add(elf.total).filter(|&sum| sum <= self.limit)
+            });
+
+        match top {
+            Some(_) => Ok(()),
+            None => Err(Overflow::Top {
+                count: self.maximums.len(),
+                limit: self.limit,
+            }
+            .into()),
+        }
+    }
+
+    /// Counts the elf being read if it has any items, without checking that there were any elves at all.
+    pub(crate) fn flush(&mut self) {
+        if !self.current.items.is_empty() {
+            self.finish_elf();
+        }
+    }
+
+    /// The number of elves read so far.
+    pub(crate) fn elves(&self) -> usize {
+        self.current.index - 1
+    }
+
+    /// The number of lines read so far.
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Takes over the elves of a parser that read the part of the input right after the one this parser read,
+    /// which has to end with a blank line, numbering them and their lines as if this parser had read them.
+    pub(crate) fn append(&mut self, next: Parser) {
+        let elves = self.elves();
+        let lines = self.line;
+        let shift = |elf: Elf| elf.shifted(elves, lines);
+
+        for Reverse(Ranked(elf)) in next.maximums {
+            self.keep(shift(elf));
+        }
+        if let (Some(inventory), Some(next)) = (&mut self.inventory, next.inventory) {
+            next.elves()
+                .iter()
+                .cloned()
+                .for_each(|elf| inventory.push(shift(elf)));
+        }
+        if let (Some(statistics), Some(next)) = (&mut self.statistics, next.statistics) {
+            statistics.merge(next.shifted(elves, lines));
+        }
+        if let (Some(totals), Some(next)) = (&mut self.totals, next.totals) {
+            totals.extend(next);
+        }
+        self.current = shift(next.current);
+        self.line += next.line;
+    }
+
+    fn finish_elf(&mut self) {
+        let next = Elf::new(self.current.index + 1, self.line + 1);
+        let elf = std::mem::replace(&mut self.current, next);
+
+        if let Some(statistics) = &mut self.statistics {
+            statistics.add(&elf);
+        }
+        if let Some(inventory) = &mut self.inventory {
+            inventory.push(elf.clone());
+        }
+        if let Some(totals) = &mut self.totals {
+            totals.push(elf.total);
+        }
+
+        self.keep(elf);
+    }
+
+    /// Adds an elf to the top ones if it ranks high enough.
+    fn keep(&mut self, elf: Elf) {
+        let elf = Reverse(Ranked(elf));
+
+        if self.maximums.len() < self.top_n {
+            self.maximums.push(elf);
+        } else if self.maximums.peek().is_some_and(|lowest| elf < *lowest) {
+            // the lowest ranked elf makes way for this one
+            self.maximums.pop();
+            self.maximums.push(elf);
+        }
+    }
+
+    /// The calories of all the top elves together, which `finish` checked to be within the limit.
+    pub fn get_max(&self) -> u128 {
+        self.maximums
+            .iter()
+            .map(|Reverse(Ranked(elf))| elf.total)
+            .sum()
+    }
+
+    /// The calories of the `k` heaviest elves together, or of all of them when there are fewer.
+    ///
+    /// `None` when `k` is above the `top_n` the parser was made for.
+    pub fn top_sum(&self, k: usize) -> Option<u128> {
+        (k <= self.top_n).then(|| {
+            self.ranked_elves()
+                .into_iter()
+                .take(k)
+                .map(|elf| elf.total)
+                .sum()
+        })
+    }
+
+    /// The `k`-th heaviest elf counting from 1, `None` when there are fewer elves or `k` is above `top_n`.
+    pub fn kth_largest(&self, k: usize) -> Option<&Elf> {
+        match k {
+            0 => None,
+            k if k > self.top_n => None,
+            k => self.ranked_elves().get(k - 1).copied(),
+        }
+    }
+
+    /// How many elves carry more than `calories` once the input is finished, `None` unless the parser
+    /// kept every total.
+    pub fn count_exceeding(&self, calories: u128) -> Option<usize> {
+        self.totals
+            .as_ref()
+            .map(|totals| totals.len() - totals.partition_point(|&total| total <= calories))
+    }
+
+    /// The elves carrying the most calories as `(elf, calories, items)`, the heaviest first
+    /// and elves carrying as much in input order.
+    pub fn ranked(&self) -> Vec<(usize, u128, &[u64])> {
+        self.ranked_elves()
+            .into_iter()
+            .map(|elf| (elf.index, elf.total, elf.items.as_slice()))
+            .collect()
+    }
+
+    /// The same elves as `ranked`, with the lines of their blocks.
+    pub fn ranked_elves(&self) -> Vec<&Elf> {
+        let mut elves = self
+            .maximums
+            .iter()
+            .map(|Reverse(ranked)| ranked)
+            .collect::<Vec<_>>();
+
+        elves.sort_by(|a, b| b.cmp(a));
+
+        elves.into_iter().map(|Ranked(elf)| elf).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// The example of the puzzle, shared with the tests of the other modules.
+    pub const INPUT: &str = r#"1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+"#;
+
+    /// Feeds the example to a parser set up for a test.
+    pub fn read_example(parser: Parser) -> Parser {
+        parser
+            .read_from(BufReader::new(INPUT.as_bytes()), &Diagnostics::strict())
+            .unwrap()
+    }
+
+    fn read(input: &str, top_n: usize) -> Parser {
+        Parser::new(top_n)
+            .with_totals()
+            .read_from(BufReader::new(input.as_bytes()), &Diagnostics::strict())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parser_top_1() {
+        let parser = read(INPUT, 1);
+
+        assert_eq!(parser.get_max(), 24000);
+    }
+
+    #[test]
+    fn test_parser_top_3() {
+        let parser = read(INPUT, 3);
+
+        assert_eq!(parser.get_max(), 45000);
+        assert_eq!(
+            parser.ranked(),
+            vec![
+                (4, 24000, &[7000, 8000, 9000][..]),
+                (3, 11000, &[5000, 6000][..]),
+                (5, 10000, &[10000][..])
+            ]
+        );
+        assert_eq!(parser.ranked_elves()[0].lines, 10..13);
+    }
+
+    #[test]
+    fn test_ranked_ties() {
+        let mut parser = Parser::new(3);
+
+        // the second and third elf carry as much as the first, the third one loses the tie
+        for line in ["3000", "", "1000", "2000", "", "3000", "", "500"] {
+            parser.parse(line).unwrap();
+        }
+        parser.finish().unwrap();
+
+        assert_eq!(
+            parser
+                .ranked()
+                .iter()
+                .map(|(elf, calories, _)| (*elf, *calories))
+                .collect::<Vec<_>>(),
+            vec![(1, 3000), (2, 3000), (3, 3000)]
+        );
+        assert_eq!(parser.ranked_elves()[1].lines, 3..5);
+    }
+
+    #[test]
+    fn test_queries() {
+        let parser = read(INPUT, 3);
+
+        assert_eq!(parser.top_sum(0), Some(0));
+        assert_eq!(parser.top_sum(1), Some(24000));
+        assert_eq!(parser.top_sum(2), Some(35000));
+        assert_eq!(parser.top_sum(3), Some(45000));
+        assert_eq!(parser.top_sum(4), None);
+
+        assert_eq!(parser.kth_largest(0), None);
+        assert_eq!(parser.kth_largest(2).unwrap().index, 3);
+        assert_eq!(parser.kth_largest(3).unwrap().total, 10000);
+        assert_eq!(parser.kth_largest(4), None);
+
+        assert_eq!(parser.count_exceeding(0), Some(5));
+        assert_eq!(parser.count_exceeding(10000), Some(2));
+        assert_eq!(parser.count_exceeding(10999), Some(2));
+        assert_eq!(parser.count_exceeding(24000), Some(0));
+    }
+
+    #[test]
+    fn test_only_top_elves_kept() {
+        let parser =
+            Parser::read(BufReader::new(INPUT.as_bytes()), 2, &Diagnostics::strict()).unwrap();
+
+        // nothing grows with the number of elves unless asked for
+        assert_eq!(parser.count_exceeding(0), None);
+        assert!(parser.inventory().is_none());
+        assert_eq!(parser.top_sum(2), Some(35000));
+    }
+
+    #[test]
+    fn test_queries_ties() {
+        let parser = read("3000\n\n1000\n2000\n\n3000\n\n500\n", 2);
+
+        // only two of the three elves carrying 3000 make the top, but all of them count
+        assert_eq!(parser.top_sum(2), Some(6000));
+        assert_eq!(parser.kth_largest(2).unwrap().index, 2);
+        assert_eq!(parser.count_exceeding(2999), Some(3));
+        assert_eq!(parser.count_exceeding(3000), Some(0));
+    }
+
+    #[test]
+    fn test_fewer_elves_than_k() {
+        let parser = read("1000\n\n500\n", 5);
+
+        // an elf lighter than the ones before still makes the top while it isn't full
+        assert_eq!(parser.ranked().len(), 2);
+        assert_eq!(parser.top_sum(5), Some(1500));
+        assert_eq!(parser.top_sum(2), Some(1500));
+        assert_eq!(parser.kth_largest(2).unwrap().total, 500);
+        assert_eq!(parser.kth_largest(3), None);
+    }
+
+    #[test]
+    fn test_u64_boundaries() {
+        let max = u64::MAX;
+
+        assert_eq!(read(&format!("{max}\n"), 1).get_max(), max as u128);
+        assert_eq!(
+            read(&format!("{}\n1\n\n5\n", max - 1), 1).get_max(),
+            max as u128
+        );
+        assert_eq!(
+            read(&format!("{}\n\n5\n", max - 5), 2).get_max(),
+            max as u128
+        );
+
+        let error = Parser::read(
+            BufReader::new(format!("5\n\n{max}\n1\n").as_bytes()),
+            1,
+            &Diagnostics::lenient(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.downcast_ref::<Overflow>(),
+            Some(&Overflow::Elf {
+                index: 2,
+                line: 4,
+                limit: max as u128
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            format!("line 4: elf 2 carries more than {max} calories, set `wide = true` for wider totals")
+        );
+
+        // each elf fits, but not the two of them together
+        let input = format!("{max}\n\n{max}\n");
+        assert_eq!(read(&input, 1).get_max(), max as u128);
+        let error = Parser::read(BufReader::new(input.as_bytes()), 2, &Diagnostics::strict())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.downcast_ref::<Overflow>(),
+            Some(&Overflow::Top {
+                count: 2,
+                limit: max as u128
+            })
+        );
+    }
+
+    #[test]
+    fn test_wide() {
+        let max = u64::MAX;
+        let input = format!("{max}\n{max}\n1\n\n{max}\n");
+
+        let parser = Parser::new(2)
+            .with_wide(true)
+            .with_totals()
+            .read_from(BufReader::new(input.as_bytes()), &Diagnostics::strict())
+            .unwrap();
+
+        assert_eq!(parser.top_sum(1), Some(2 * max as u128 + 1));
+        assert_eq!(parser.get_max(), 3 * max as u128 + 1);
+        assert_eq!(parser.count_exceeding(max as u128), Some(1));
+    }
+
+    #[test]
+    fn test_names() {
+        let parser = read("# Legolas\n1000\n\n2000\n\n#Gimli \n500\n600\n", 3);
+
+        assert_eq!(
+            parser
+                .ranked_elves()
+                .iter()
+                .map(|elf| elf.name())
+                .collect::<Vec<_>>(),
+            vec!["2nd elf", "Gimli", "Legolas"]
+        );
+        assert_eq!(parser.ranked_elves()[1].lines, 7..9);
+
+        let read_with = |prefix: &str, input: &str| {
+            let mut parser = Parser::new(1).with_name_prefix(prefix);
+            input.lines().try_for_each(|line| parser.parse(line))?;
+            parser.finish().map(|()| parser)
+        };
+
+        let parser = read_with("Name:", "Name: Frodo\n1000\n").unwrap();
+        assert_eq!(parser.ranked_elves()[0].name(), "Frodo");
+
+        // without a prefix a name line is a bad item like any other
+        let error = read_with("", "# Frodo\n1000\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r##"line 1, column 1: expected an integer, found "# Frodo""##
+        );
+
+        for (input, line) in [("# Frodo\n1000\n# Sam\n", 3), ("# Frodo\n# Sam\n1000\n", 2)] {
+            let error = read_with("#", input).err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("line {line}, column 1: expected calories, found a name")
+            );
+        }
+
+        let error = read_with("#", "1000\n\n# Sam\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected calories, found end of input"
+        );
+    }
+
+    #[test]
+    fn test_ordinal() {
+        for (n, expected) in [
+            (1, "1st"),
+            (2, "2nd"),
+            (3, "3rd"),
+            (4, "4th"),
+            (11, "11th"),
+            (12, "12th"),
+            (13, "13th"),
+            (21, "21st"),
+            (102, "102nd"),
+            (111, "111th"),
+        ] {
+            assert_eq!(ordinal(n), expected);
+        }
+    }
+
+    #[test]
+    fn test_parser_error_position() {
+        let mut parser = Parser::new(1);
+
+        parser.parse("1000").unwrap();
+        parser.parse("").unwrap();
+
+        let error = parser.parse("20x0").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 3, column 3: expected end of line, found "x0""#
+        );
+    }
+
+    #[test]
+    fn test_last_elf() {
+        // the last elf counts with or without a blank line after it
+        assert_eq!(read("1000\n\n5000\n", 1).get_max(), 5000);
+        assert_eq!(read("1000\n\n5000\n\n", 1).get_max(), 5000);
+        assert_eq!(read(&format!("{INPUT}\n"), 3).get_max(), 45000);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let read = |input: &str, diagnostics: &Diagnostics| {
+            Parser::read(BufReader::new(input.as_bytes()), 1, diagnostics)
+        };
+
+        let error = read("", &Diagnostics::strict()).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected calories, found end of input"
+        );
+
+        let error = read("1000\n\n\n2000\n", &Diagnostics::strict())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected calories, found a blank line"
+        );
+
+        // in lenient mode the extra blank line is skipped rather than making an elf without items
+        let diagnostics = Diagnostics::lenient();
+        let parser = read("\n1000\n\n\n2000\n", &diagnostics).unwrap();
+        assert_eq!(parser.get_max(), 2000);
+        assert_eq!(parser.ranked_elves()[0].index, 2);
+        assert_eq!(diagnostics.warnings().len(), 2);
+    }
+}
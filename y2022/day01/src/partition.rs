@@ -0,0 +1,363 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use common::{Budget, BudgetExceeded};
+
+/// Inputs with up to this many elves are partitioned exactly, larger ones by heuristics.
+pub const EXACT_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+    /// in input order
+    pub elves: Vec<usize>,
+    pub calories: u128,
+}
+
+/// The elves split into teams, with how far the heaviest team may be from the best split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    /// the heaviest first
+    pub teams: Vec<Team>,
+    /// no split has a heaviest team lighter than this
+    pub lower_bound: u128,
+    /// whether no split has a lighter heaviest team
+    pub optimal: bool,
+    /// whether the exhaustive search ran out of budget, leaving the best split found until then
+    pub interrupted: bool,
+}
+
+impl Partition {
+    pub fn largest(&self) -> u128 {
+        self.teams.first().map_or(0, |team| team.calories)
+    }
+
+    /// How much heavier the heaviest team is than the lower bound, relative to the bound.
+    ///
+    /// An optimal partition can still have a gap, when the bound isn't reached by any split.
+    pub fn gap(&self) -> f64 {
+        match self.lower_bound {
+            0 => 0.0,
+            bound => (self.largest() - bound) as f64 / bound as f64,
+        }
+    }
+}
+
+/// Splits the elves, given as `(elf, calories)`, into `k` teams keeping the heaviest team as light as possible.
+///
+/// Up to `exact_limit` elves are searched exhaustively within the budget, starting from the better of
+/// Karmarkar–Karp differencing and greedily giving the heaviest elf left to the lightest team, which is
+/// what's taken for more elves or once the budget is spent.
+pub fn partition(
+    elves: &[(usize, u128)],
+    k: usize,
+    exact_limit: usize,
+    budget: &Budget,
+) -> Partition {
+    let k = k.max(1);
+    let mut sorted = elves.to_vec();
+    sorted.sort_by_key(|&(elf, calories)| (Reverse(calories), elf));
+
+    let sum = sorted.iter().map(|&(_, calories)| calories).sum::<u128>();
+    let heaviest = sorted.first().map_or(0, |&(_, calories)| calories);
+    let lower_bound = heaviest.max(sum.div_ceil(k as u128));
+
+    let mut best = [differencing(&sorted, k), greedy(&sorted, k)]
+        .into_iter()
+        .min_by_key(|assignment| largest(&loads(&sorted, assignment, k)))
+        .unwrap_or_default();
+
+    let mut exact = sorted.len() <= exact_limit;
+    let mut interrupted = false;
+    if exact {
+        let mut search = Search {
+            elves: &sorted,
+            lower_bound,
+            best_largest: largest(&loads(&sorted, &best, k)),
+            best: best.clone(),
+            assignment: Vec::with_capacity(sorted.len()),
+            loads: vec![0; k],
+            budget,
+        };
+        interrupted = search.run().is_err();
+        exact = !interrupted;
+        best = search.best;
+    }
+
+    let mut teams = vec![
+        Team {
+            elves: Vec::new(),
+            calories: 0,
+        };
+        k
+    ];
+    for (&(elf, calories), &team) in sorted.iter().zip(&best) {
+        teams[team].elves.push(elf);
+        teams[team].calories += calories;
+    }
+    teams.iter_mut().for_each(|team| team.elves.sort_unstable());
+    teams.sort_by_key(|team| (Reverse(team.calories), team.elves.first().copied()));
+
+    let largest = teams.first().map_or(0, |team| team.calories);
+
+    Partition {
+        teams,
+        lower_bound,
+        optimal: exact || largest == lower_bound,
+        interrupted,
+    }
+}
+
+/// The calories of each team, for the team of each elf.
+fn loads(elves: &[(usize, u128)], assignment: &[usize], k: usize) -> Vec<u128> {
+    let mut loads = vec![0; k];
+    for (&(_, calories), &team) in elves.iter().zip(assignment) {
+        loads[team] += calories;
+    }
+    loads
+}
+
+fn largest(loads: &[u128]) -> u128 {
+    loads.iter().copied().max().unwrap_or_default()
+}
+
+/// Longest processing time first: each elf, the heaviest first, joins the lightest team.
+fn greedy(elves: &[(usize, u128)], k: usize) -> Vec<usize> {
+    let mut loads = vec![0; k];
+
+    elves
+        .iter()
+        .map(|&(_, calories)| {
+            let (team, _) = loads
+                .iter()
+                .enumerate()
+                .min_by_key(|&(team, load)| (*load, team))
+                .unwrap();
+            loads[team] += calories;
+            team
+        })
+        .collect()
+}
+
+/// Teams of some of the elves, the heaviest first, kept in a heap by how uneven they are.
+struct Subset {
+    loads: Vec<u128>,
+    members: Vec<Vec<usize>>,
+}
+
+impl Subset {
+    fn spread(&self) -> u128 {
+        self.loads[0] - self.loads[self.loads.len() - 1]
+    }
+
+    /// Puts the heaviest teams of one subset together with the lightest of the other.
+    fn combine(mut self, mut other: Self) -> Self {
+        let mut teams = self
+            .loads
+            .iter()
+            .zip(self.members.iter_mut())
+            .zip(other.loads.iter().rev().zip(other.members.iter_mut().rev()))
+            .map(|((a, a_members), (b, b_members))| {
+                a_members.append(b_members);
+                (a + b, std::mem::take(a_members))
+            })
+            .collect::<Vec<_>>();
+        teams.sort_by_key(|&(load, _)| Reverse(load));
+
+        let (loads, members) = teams.into_iter().unzip();
+        self.loads = loads;
+        self.members = members;
+        self
+    }
+}
+
+impl Ord for Subset {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.spread().cmp(&other.spread())
+    }
+}
+
+impl PartialOrd for Subset {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Subset {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Subset {}
+
+/// Karmarkar–Karp differencing: the two most uneven subsets are combined until one is left.
+fn differencing(elves: &[(usize, u128)], k: usize) -> Vec<usize> {
+    let mut heap = elves
+        .iter()
+        .enumerate()
+        .map(|(position, &(_, calories))| {
+            let mut loads = vec![0; k];
+            let mut members = vec![Vec::new(); k];
+            loads[0] = calories;
+            members[0].push(position);
+            Subset { loads, members }
+        })
+        .collect::<BinaryHeap<_>>();
+
+    while heap.len() > 1 {
+        let (a, b) = (heap.pop().unwrap(), heap.pop().unwrap());
+        heap.push(a.combine(b));
+    }
+
+    let mut assignment = vec![0; elves.len()];
+    for (team, members) in heap
+        .pop()
+        .into_iter()
+        .flat_map(|subset| subset.members)
+        .enumerate()
+    {
+        for position in members {
+            assignment[position] = team;
+        }
+    }
+    assignment
+}
+
+/// Branch and bound over the team of each elf, the heaviest first.
+struct Search<'a> {
+    elves: &'a [(usize, u128)],
+    lower_bound: u128,
+    best_largest: u128,
+    best: Vec<usize>,
+    assignment: Vec<usize>,
+    loads: Vec<u128>,
+    budget: &'a Budget,
+}
+
+impl Search<'_> {
+    fn run(&mut self) -> Result<(), BudgetExceeded> {
+        // nothing beats a split reaching the bound
+        if self.best_largest == self.lower_bound {
+            return Ok(());
+        }
+
+        self.budget.check(|| {
+            format!(
+                "{} of {} elves placed, the heaviest team at {}",
+                self.assignment.len(),
+                self.elves.len(),
+                self.best_largest
+            )
+        })?;
+
+        let Some(&(_, calories)) = self.elves.get(self.assignment.len()) else {
+            self.best_largest = largest(&self.loads);
+            self.best = self.assignment.clone();
+            return Ok(());
+        };
+
+        for team in 0..self.loads.len() {
+            let load = self.loads[team];
+
+            // only strictly better splits are searched, and teams carrying as much are interchangeable
+            if load + calories >= self.best_largest || self.loads[..team].contains(&load) {
+                continue;
+            }
+
+            self.loads[team] += calories;
+            self.assignment.push(team);
+            let searched = self.run();
+            self.assignment.pop();
+            self.loads[team] -= calories;
+            searched?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn elves(calories: &[u128]) -> Vec<(usize, u128)> {
+        (1..).zip(calories.iter().copied()).collect()
+    }
+
+    fn calories(partition: &Partition) -> Vec<u128> {
+        partition.teams.iter().map(|team| team.calories).collect()
+    }
+
+    #[test]
+    fn test_exact() {
+        let partition = partition(
+            &elves(&[6000, 4000, 11000, 24000, 10000]),
+            2,
+            EXACT_LIMIT,
+            &Budget::unlimited(),
+        );
+
+        assert_eq!(calories(&partition), vec![28000, 27000]);
+        assert_eq!(partition.teams[0].elves, vec![2, 4]);
+        assert_eq!(partition.lower_bound, 27500);
+        assert!(partition.optimal);
+        assert!((partition.gap() - 500.0 / 27500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_heuristics() {
+        // differencing splits these 16 and 14, greedily 17 and 13, while 15 and 15 can be reached
+        let input = elves(&[8, 7, 6, 5, 4]);
+
+        let heuristic = partition(&input, 2, 0, &Budget::unlimited());
+        assert_eq!(calories(&heuristic), vec![16, 14]);
+        assert!(!heuristic.optimal);
+        assert!((heuristic.gap() - 1.0 / 15.0).abs() < 1e-9);
+
+        let exact = partition(&input, 2, EXACT_LIMIT, &Budget::unlimited());
+        assert_eq!(calories(&exact), vec![15, 15]);
+        assert_eq!(exact.gap(), 0.0);
+    }
+
+    #[test]
+    fn test_out_of_budget() {
+        let budget = Budget::unlimited();
+        budget.cancel();
+
+        // the search stops right away, leaving the heuristic split
+        let partition = partition(&elves(&[8, 7, 6, 5, 4]), 2, EXACT_LIMIT, &budget);
+        assert_eq!(calories(&partition), vec![16, 14]);
+        assert!(partition.interrupted);
+        assert!(!partition.optimal);
+    }
+
+    #[test]
+    fn test_more_teams_than_elves() {
+        let partition = partition(&elves(&[3, 9]), 4, 0, &Budget::unlimited());
+
+        assert_eq!(calories(&partition), vec![9, 3, 0, 0]);
+        assert_eq!(partition.teams[0].elves, vec![2]);
+        assert!(partition.optimal);
+    }
+
+    #[test]
+    fn test_large() {
+        let input = (1..=500)
+            .map(|elf| (elf, (elf as u128 * 7919) % 70000))
+            .collect::<Vec<_>>();
+        let partition = partition(&input, 7, EXACT_LIMIT, &Budget::unlimited());
+
+        let mut assigned = partition
+            .teams
+            .iter()
+            .flat_map(|team| team.elves.iter().copied())
+            .collect::<Vec<_>>();
+        assigned.sort_unstable();
+        assert_eq!(assigned, (1..=500).collect::<Vec<_>>());
+
+        assert!(partition.largest() >= partition.lower_bound);
+        assert!(partition.gap() < 0.001, "{}", partition.gap());
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+};
+
+use crate::parser::{Elf, Overflow};
+
+/// The elves by calories, kept in order while they eat snacks, pick up new ones or leave.
+///
+/// Every elf is found by its index and ranked by `(calories, index)` in an ordered set, so each update
+/// takes logarithmic time and the top `n` elves are the first `n` of the set.
+#[derive(Debug, Clone)]
+pub struct Ranking {
+    elves: HashMap<usize, Elf>,
+    order: BTreeSet<(Reverse<u128>, usize)>,
+    /// the limit of the parser the elves come from, which no elf may grow past
+    limit: u128,
+}
+
+impl Ranking {
+    /// Ranks elves by their index, which can only be taken by one of them.
+    pub fn new(elves: impl IntoIterator<Item = Elf>, limit: u128) -> anyhow::Result<Self> {
+        let mut ranking = Self {
+            elves: HashMap::new(),
+            order: BTreeSet::new(),
+            limit,
+        };
+
+        for elf in elves {
+            if ranking.elves.contains_key(&elf.index) {
+                anyhow::bail!("elf {} is ranked twice", elf.index);
+            }
+
+            ranking.order.insert((Reverse(elf.total), elf.index));
+            ranking.elves.insert(elf.index, elf);
+        }
+
+        Ok(ranking)
+    }
+
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn get(&self, elf: usize) -> Option<&Elf> {
+        self.elves.get(&elf)
+    }
+
+    /// Gives an elf another item. The lines of the elf keep pointing at its block of the input, the last
+    /// of which an overflow is reported at.
+    pub fn add_item(&mut self, elf: usize, calories: u64) -> anyhow::Result<()> {
+        let limit = self.limit;
+
+        self.update(elf, |elf| {
+            elf.total = elf
+                .total
+                .checked_add(calories as u128)
+                .filter(|&total| total <= limit)
+                .ok_or(Overflow::Elf {
+                    index: elf.index,
+                    line: elf.lines.clone().last().unwrap_or(elf.lines.start),
+                    limit,
+                })?;
+            elf.items.push(calories);
+            Ok(())
+        })
+    }
+
+    /// Takes an item of `calories` away from an elf, which may be left carrying nothing.
+    pub fn remove_item(&mut self, elf: usize, calories: u64) -> anyhow::Result<()> {
+        self.update(elf, |elf| {
+            let position = elf
+                .items
+                .iter()
+                .position(|&item| item == calories)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("elf {} carries no item of {calories} calories", elf.index)
+                })?;
+
+            elf.items.remove(position);
+            elf.total -= calories as u128;
+            Ok(())
+        })
+    }
+
+    /// Takes an elf out of the ranking altogether.
+    pub fn drop_elf(&mut self, elf: usize) -> Option<Elf> {
+        let elf = self.elves.remove(&elf)?;
+        self.order.remove(&(Reverse(elf.total), elf.index));
+        Some(elf)
+    }
+
+    /// The `n` elves carrying the most calories, the heaviest first and elves carrying as much in input order.
+    pub fn top(&self, n: usize) -> impl Iterator<Item = &Elf> {
+        self.order
+            .iter()
+            .take(n)
+            .map(|(_, index)| &self.elves[index])
+    }
+
+    /// The calories of the `n` heaviest elves together, which may not add up past the limit.
+    pub fn top_sum(&self, n: usize) -> Result<u128, Overflow> {
+        self.order
+            .iter()
+            .take(n)
+            .try_fold(0u128, |sum, (Reverse(total), _)| {
+                sum.checked_add(*total)
+                    .filter(|&sum| sum <= self.limit)
+                    .ok_or(Overflow::Top {
+                        count: n,
+                        limit: self.limit,
+                    })
+            })
+    }
+
+    /// Changes an elf, moving it to its new place in the order.
+    fn update(
+        &mut self,
+        elf: usize,
+        change: impl FnOnce(&mut Elf) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let elf = self
+            .elves
+            .get_mut(&elf)
+            .ok_or_else(|| anyhow::anyhow!("there's no elf {elf}"))?;
+        let before = (Reverse(elf.total), elf.index);
+
+        change(elf)?;
+
+        self.order.remove(&before);
+        self.order.insert((Reverse(elf.total), elf.index));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::parser::{tests::read_example, Parser};
+
+    fn ranking() -> Ranking {
+        let parser = read_example(Parser::new(3).with_inventory());
+
+        Ranking::new(
+            parser.inventory().unwrap().elves().iter().cloned(),
+            parser.limit(),
+        )
+        .unwrap()
+    }
+
+    fn top(ranking: &Ranking, n: usize) -> Vec<(usize, u128)> {
+        ranking.top(n).map(|elf| (elf.index, elf.total)).collect()
+    }
+
+    #[test]
+    fn test_updates() {
+        let mut ranking = ranking();
+
+        assert_eq!(ranking.top_sum(3).unwrap(), 45000);
+        assert_eq!(top(&ranking, 3), vec![(4, 24000), (3, 11000), (5, 10000)]);
+
+        // the second elf picks up a snack and passes the fifth and third ones
+        ranking.add_item(2, 8000).unwrap();
+        assert_eq!(top(&ranking, 3), vec![(4, 24000), (2, 12000), (3, 11000)]);
+
+        // the heaviest elf eats two snacks, and then leaves
+        ranking.remove_item(4, 9000).unwrap();
+        ranking.remove_item(4, 8000).unwrap();
+        assert_eq!(ranking.get(4).unwrap().items, vec![7000]);
+        assert_eq!(top(&ranking, 2), vec![(2, 12000), (3, 11000)]);
+
+        assert_eq!(ranking.drop_elf(2).unwrap().total, 12000);
+        assert_eq!(ranking.drop_elf(2), None);
+        assert_eq!(ranking.len(), 4);
+        assert_eq!(ranking.top_sum(3).unwrap(), 11000 + 10000 + 7000);
+    }
+
+    #[test]
+    fn test_ties_and_empty_elves() {
+        let mut ranking = ranking();
+
+        // catching up with the fifth elf, the third one stays ahead of it by coming first
+        ranking.remove_item(3, 6000).unwrap();
+        ranking.add_item(5, 0).unwrap();
+        ranking.add_item(3, 5000).unwrap();
+        assert_eq!(top(&ranking, 3), vec![(4, 24000), (3, 10000), (5, 10000)]);
+
+        ranking.remove_item(2, 4000).unwrap();
+        assert_eq!(ranking.get(2).unwrap().total, 0);
+        assert_eq!(top(&ranking, 5).last(), Some(&(2, 0)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut ranking = ranking();
+
+        assert_eq!(
+            ranking.add_item(9, 1000).unwrap_err().to_string(),
+            "there's no elf 9"
+        );
+        assert_eq!(
+            ranking.remove_item(1, 4000).unwrap_err().to_string(),
+            "elf 1 carries no item of 4000 calories"
+        );
+
+        let error = ranking.add_item(4, u64::MAX).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<Overflow>(),
+            Some(&Overflow::Elf {
+                index: 4,
+                line: 12,
+                limit: u64::MAX as u128
+            })
+        );
+
+        // a failed update leaves the ranking as it was
+        assert_eq!(ranking.top_sum(5).unwrap(), 55000);
+        assert_eq!(ranking.top(5).count(), 5);
+    }
+
+    #[test]
+    fn test_wide_top_sum() {
+        let elf = |index, total| Elf {
+            index,
+            name: None,
+            lines: index..index + 1,
+            items: Vec::new(),
+            total,
+        };
+        let ranking =
+            Ranking::new([elf(1, u128::MAX - 1), elf(2, 1), elf(3, 1)], u128::MAX).unwrap();
+
+        assert_eq!(ranking.top_sum(2), Ok(u128::MAX));
+        assert_eq!(
+            ranking.top_sum(3),
+            Err(Overflow::Top {
+                count: 3,
+                limit: u128::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn test_duplicate_elves() {
+        let ranking = ranking();
+        let elves = ranking.top(5).chain(ranking.top(1)).cloned();
+
+        assert_eq!(
+            Ranking::new(elves, u128::MAX).unwrap_err().to_string(),
+            "elf 4 is ranked twice"
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::parser::Elf;
+
+/// The longest bar of the histogram.
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Relative error bounded quantiles over a stream of values, keeping one counter per bucket of values
+/// within `accuracy` of each other instead of the values themselves.
+///
+/// Buckets grow geometrically, so a few hundred of them cover every `u64`.
+#[derive(Debug, Clone)]
+pub struct Sketch {
+    gamma: f64,
+    buckets: BTreeMap<i32, u64>,
+    zeros: u64,
+    count: u64,
+}
+
+impl Sketch {
+    /// `accuracy` is the relative error of the quantiles, 0.01 for 1%.
+    pub fn new(accuracy: f64) -> Self {
+        Self {
+            gamma: (1.0 + accuracy) / (1.0 - accuracy),
+            buckets: BTreeMap::new(),
+            zeros: 0,
+            count: 0,
+        }
+    }
+
+    pub fn add(&mut self, value: u128) {
+        self.count += 1;
+
+        match value {
+            0 => self.zeros += 1,
+            value => {
+                let bucket = (value as f64).log(self.gamma).ceil() as i32;
+                *self.buckets.entry(bucket).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// The value of each bucket and how many values fell into it, smallest first.
+    pub fn buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        let zeros = (self.zeros > 0).then_some((0.0, self.zeros));
+
+        zeros.into_iter().chain(
+            self.buckets
+                .iter()
+                .map(|(&bucket, &count)| (self.value(bucket), count)),
+        )
+    }
+
+    /// `q` from 0 to 1.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = (q.clamp(0.0, 1.0) * (self.count - 1) as f64) as u64;
+        let mut seen = 0;
+
+        self.buckets()
+            .find(|(_, count)| {
+                seen += count;
+                seen > rank
+            })
+            .map(|(value, _)| value)
+    }
+
+    /// Adds the values of a sketch of the same accuracy.
+    pub fn merge(&mut self, other: &Self) {
+        for (&bucket, &count) in &other.buckets {
+            *self.buckets.entry(bucket).or_insert(0) += count;
+        }
+        self.zeros += other.zeros;
+        self.count += other.count;
+    }
+
+    /// The middle of a bucket, within `accuracy` of every value in it.
+    fn value(&self, bucket: i32) -> f64 {
+        2.0 * self.gamma.powi(bucket) / (self.gamma + 1.0)
+    }
+}
+
+/// Where the elf totals are kept for the quantiles.
+#[derive(Debug, Clone)]
+pub enum Totals {
+    Exact(Vec<u128>),
+    Sketch(Sketch),
+}
+
+/// Summary of a calorie inventory, fed one elf at a time as the parser finishes them.
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    elves: u64,
+    items: u64,
+    min_items: usize,
+    max_items: usize,
+    min: u128,
+    max: u128,
+    /// the first elves carrying the least and the most
+    lightest: Option<Elf>,
+    heaviest: Option<Elf>,
+    /// running mean and sum of squared deviations of the totals, as in Welford's algorithm
+    mean: f64,
+    squares: f64,
+    totals: Totals,
+}
+
+impl Statistics {
+    /// Keeps every total, for exact quantiles.
+    pub fn exact() -> Self {
+        Self::new(Totals::Exact(Vec::new()))
+    }
+
+    /// Keeps a sketch of the totals, for inputs with more elves than fit in memory.
+    pub fn streaming(accuracy: f64) -> Self {
+        Self::new(Totals::Sketch(Sketch::new(accuracy)))
+    }
+
+    fn new(totals: Totals) -> Self {
+        Self {
+            elves: 0,
+            items: 0,
+            min_items: usize::MAX,
+            max_items: 0,
+            min: u128::MAX,
+            max: 0,
+            lightest: None,
+            heaviest: None,
+            mean: 0.0,
+            squares: 0.0,
+            totals,
+        }
+    }
+
+    pub fn add(&mut self, elf: &Elf) {
+        self.elves += 1;
+        self.items += elf.items.len() as u64;
+        self.min_items = self.min_items.min(elf.items.len());
+        self.max_items = self.max_items.max(elf.items.len());
+        if elf.total < self.min {
+            self.min = elf.total;
+            self.lightest = Some(elf.clone());
+        }
+        if elf.total > self.max || self.elves == 1 {
+            self.max = elf.total;
+            self.heaviest = Some(elf.clone());
+        }
+
+        let delta = elf.total as f64 - self.mean;
+        self.mean += delta / self.elves as f64;
+        self.squares += delta * (elf.total as f64 - self.mean);
+
+        match &mut self.totals {
+            Totals::Exact(totals) => totals.push(elf.total),
+            Totals::Sketch(sketch) => sketch.add(elf.total),
+        }
+    }
+
+    /// Statistics of the same kind that haven't seen any elf.
+    pub(crate) fn empty(&self) -> Self {
+        Self::new(match &self.totals {
+            Totals::Exact(_) => Totals::Exact(Vec::new()),
+            Totals::Sketch(sketch) => Totals::Sketch(Sketch {
+                buckets: BTreeMap::new(),
+                zeros: 0,
+                count: 0,
+                ..*sketch
+            }),
+        })
+    }
+
+    /// Takes over the statistics of the elves read after the ones seen so far, already numbered as if
+    /// they had been read by the same parser.
+    pub(crate) fn merge(&mut self, other: Statistics) {
+        if other.elves == 0 {
+            return;
+        }
+
+        let elves = (self.elves + other.elves) as f64;
+        let delta = other.mean - self.mean;
+        self.squares +=
+            other.squares + delta * delta * self.elves as f64 * other.elves as f64 / elves;
+        self.mean += delta * other.elves as f64 / elves;
+
+        // the first elf wins a tie, as when reading them one by one
+        if other.min < self.min {
+            self.min = other.min;
+            self.lightest = other.lightest;
+        }
+        if other.max > self.max || self.elves == 0 {
+            self.max = other.max;
+            self.heaviest = other.heaviest;
+        }
+        self.elves += other.elves;
+        self.items += other.items;
+        self.min_items = self.min_items.min(other.min_items);
+        self.max_items = self.max_items.max(other.max_items);
+
+        match (&mut self.totals, other.totals) {
+            (Totals::Exact(totals), Totals::Exact(other)) => totals.extend(other),
+            (Totals::Sketch(sketch), Totals::Sketch(other)) => sketch.merge(&other),
+            _ => unreachable!("statistics of a parser are all of the same kind"),
+        }
+    }
+
+    /// Numbers the elves as if `elves` elves and `lines` lines had been read before them.
+    pub(crate) fn shifted(mut self, elves: usize, lines: usize) -> Self {
+        self.lightest = self.lightest.map(|elf| elf.shifted(elves, lines));
+        self.heaviest = self.heaviest.map(|elf| elf.shifted(elves, lines));
+        self
+    }
+
+    pub fn elves(&self) -> u64 {
+        self.elves
+    }
+
+    pub fn items_per_elf(&self) -> Option<f64> {
+        (self.elves > 0).then(|| self.items as f64 / self.elves as f64)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.elves > 0).then_some(self.mean)
+    }
+
+    /// The standard deviation of the totals of all elves.
+    pub fn standard_deviation(&self) -> Option<f64> {
+        (self.elves > 0).then(|| (self.squares / self.elves as f64).sqrt())
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// `p` from 0 to 100, interpolating between the two closest totals when they're all kept.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        let q = (p / 100.0).clamp(0.0, 1.0);
+
+        match &self.totals {
+            Totals::Sketch(sketch) => sketch.quantile(q),
+            Totals::Exact(totals) if totals.is_empty() => None,
+            Totals::Exact(totals) => {
+                let mut sorted = totals.clone();
+                sorted.sort();
+
+                let position = q * (sorted.len() - 1) as f64;
+                let below = sorted[position.floor() as usize] as f64;
+                let above = sorted[position.ceil() as usize] as f64;
+
+                Some(below + (above - below) * position.fract())
+            }
+        }
+    }
+
+    /// How many elves carry how much, in up to `bins` ranges of equal width between the lightest and the
+    /// heaviest elf, the last one cut short at the heaviest.
+    ///
+    /// There are fewer ranges when the totals span fewer values than `bins`, so that none lies past the heaviest.
+    pub fn histogram(&self, bins: usize) -> Vec<(u128, u128, u64)> {
+        if self.elves == 0 || bins == 0 {
+            return Vec::new();
+        }
+
+        let span = (self.max - self.min).saturating_add(1);
+        let width = span.div_ceil(bins as u128);
+        let bins = span.div_ceil(width) as usize;
+        let mut counts = vec![0; bins];
+
+        let mut count = |value: f64, n: u64| {
+            let value = (value.round() as u128).clamp(self.min, self.max);
+            counts[(((value - self.min) / width) as usize).min(bins - 1)] += n;
+        };
+
+        match &self.totals {
+            Totals::Exact(totals) => totals.iter().for_each(|&total| count(total as f64, 1)),
+            Totals::Sketch(sketch) => sketch.buckets().for_each(|(value, n)| count(value, n)),
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let low = self.min + i as u128 * width;
+                (low, (low + width - 1).min(self.max), count)
+            })
+            .collect()
+    }
+
+    pub fn report(&self, percentiles: &[f64], bins: usize) -> String {
+        let mut report = format!("elves: {}\n", self.elves);
+
+        let (Some(items), Some(mean), Some(deviation)) =
+            (self.items_per_elf(), self.mean(), self.standard_deviation())
+        else {
+            return report;
+        };
+
+        writeln!(
+            report,
+            "items per elf: mean {items:.2}, min {}, max {}",
+            self.min_items, self.max_items
+        )
+        .unwrap();
+        writeln!(
+            report,
+            "calories per elf: mean {mean:.2}, min {} ({}), max {} ({}), standard deviation {deviation:.2}",
+            self.min,
+            self.lightest.as_ref().map(Elf::name).unwrap_or_default(),
+            self.max,
+            self.heaviest.as_ref().map(Elf::name).unwrap_or_default()
+        )
+        .unwrap();
+
+        if let Totals::Sketch(sketch) = &self.totals {
+            let accuracy = (sketch.gamma - 1.0) / (sketch.gamma + 1.0);
+            writeln!(
+                report,
+                "percentiles are estimated within {:.2}%",
+                accuracy * 100.0
+            )
+            .unwrap();
+        }
+
+        writeln!(report, "median: {:.0}", self.median().unwrap_or_default()).unwrap();
+        for &p in percentiles {
+            writeln!(
+                report,
+                "p{p}: {:.0}",
+                self.percentile(p).unwrap_or_default()
+            )
+            .unwrap();
+        }
+
+        let histogram = self.histogram(bins);
+        let largest = histogram.iter().map(|(_, _, count)| *count).max();
+
+        writeln!(report, "calories per elf:").unwrap();
+        for (low, high, count) in &histogram {
+            let bar = (count * HISTOGRAM_WIDTH as u64)
+                .checked_div(largest.unwrap_or(0))
+                .unwrap_or(0);
+
+            writeln!(
+                report,
+                "  {low:>8} - {high:<8} {:<width$} {count}",
+                "#".repeat(bar as usize),
+                width = HISTOGRAM_WIDTH
+            )
+            .unwrap();
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::BufReader;
+
+    use common::Diagnostics;
+
+    use super::*;
+    use crate::parser::{tests::read_example, Parser};
+
+    fn statistics(statistics: Statistics) -> Statistics {
+        read_example(Parser::new(1).with_statistics(statistics))
+            .statistics()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_exact() {
+        let statistics = statistics(Statistics::exact());
+
+        assert_eq!(statistics.elves(), 5);
+        assert_eq!(statistics.items_per_elf(), Some(2.0));
+        assert_eq!(statistics.mean(), Some(11000.0));
+        assert_eq!(statistics.median(), Some(10000.0));
+        assert_eq!(statistics.percentile(25.0), Some(6000.0));
+        assert_eq!(statistics.percentile(90.0), Some(18800.0));
+        assert!((statistics.standard_deviation().unwrap() - 6985.7).abs() < 0.1);
+
+        assert_eq!(
+            statistics.histogram(2),
+            vec![(4000, 14000, 4), (14001, 24000, 1)]
+        );
+        assert_eq!(statistics.histogram(3).last(), Some(&(17334, 24000, 1)));
+
+        let report = statistics.report(&[90.0], 2);
+        assert!(
+            report.contains("min 4000 (2nd elf), max 24000 (4th elf),"),
+            "{report}"
+        );
+        assert!(report.contains("median: 10000\np90: 18800\n"), "{report}");
+        assert!(report.ends_with(&format!("     14001 - 24000    {:<40} 1\n", "#".repeat(10))));
+    }
+
+    #[test]
+    fn test_narrow_histogram() {
+        let parser = Parser::new(1)
+            .with_statistics(Statistics::exact())
+            .read_from(
+                BufReader::new("3\n\n5\n\n5\n".as_bytes()),
+                &Diagnostics::strict(),
+            )
+            .unwrap();
+        let statistics = parser.statistics().unwrap();
+
+        // three values can't fill ten bins, and none of them may start past the heaviest elf
+        assert_eq!(
+            statistics.histogram(10),
+            vec![(3, 3, 1), (4, 4, 0), (5, 5, 2)]
+        );
+    }
+
+    #[test]
+    fn test_streaming() {
+        let statistics = statistics(Statistics::streaming(0.01));
+
+        assert_eq!(statistics.mean(), Some(11000.0));
+        let median = statistics.median().unwrap();
+        assert!((median - 10000.0).abs() <= 100.0, "{median}");
+
+        let mut sketch = Sketch::new(0.01);
+        for value in 0..=10000 {
+            sketch.add(value);
+        }
+
+        for (q, expected) in [(0.1, 1000.0), (0.5, 5000.0), (0.99, 9900.0)] {
+            let estimate = sketch.quantile(q).unwrap();
+            assert!(
+                (estimate - expected).abs() <= expected * 0.01,
+                "{q}: {estimate}"
+            );
+        }
+        assert_eq!(sketch.quantile(0.0), Some(0.0));
+        assert!(sketch.buckets().count() < 1000);
+    }
+}
@@ -0,0 +1,62 @@
+pub mod strategy1;
+pub mod strategy2;
+
+use common::{Answers, Context};
+use parsing::{Line, ParseResult};
+
+use crate::{strategy1::Strategy1, strategy2::Strategy2};
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    let mut score_part_1 = 0;
+    let mut score_part_2 = 0;
+
+    let mut trace = context
+        .explain
+        .list(|| "round scores for part 1 and part 2".to_string());
+
+    for line in parsing::lines(input.as_bytes()) {
+        let line = line?;
+        context.budget.check(|| format!("line {}", line.number))?;
+
+        let Some((game_1, game_2)) = context.diagnostics.recover(parse_round(&line))? else {
+            continue;
+        };
+
+        score_part_1 += game_1.score();
+        score_part_2 += game_2.score();
+
+        trace.entry(|| {
+            format!(
+                "round {} ({}): {} and {}",
+                line.number,
+                line.text,
+                game_1.score(),
+                game_2.score()
+            )
+        });
+    }
+
+    drop(trace);
+
+    Ok(Answers::new(score_part_1, score_part_2))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    for line in parsing::lines(input.as_bytes()) {
+        let line = line?;
+        context.budget.check(|| format!("line {}", line.number))?;
+        context.diagnostics.recover(parse_round(&line))?;
+    }
+
+    Ok(())
+}
+
+/// A round read with both strategies.
+fn parse_round(line: &Line) -> ParseResult<(Strategy1, Strategy2)> {
+    let round = Strategy1::new(&line.text)
+        .and_then(|game_1| Ok((game_1, Strategy2::new(&line.text)?)))
+        .map_err(|e| e.at_line(line.number))?;
+
+    Ok(round)
+}
@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/2
+fn main() -> anyhow::Result<()> {
+    common::run(2022, 2, y2022_day02::solve)
+}
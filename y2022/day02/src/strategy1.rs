@@ -1,22 +1,15 @@
-use anyhow::Context;
+use parsing::{Cursor, Parse, ParseResult};
 
+#[derive(Parse)]
+#[aoc(format = "{} {}")]
 pub struct Strategy1 {
     opponent_choice: Choice,
     player_choice: Choice,
 }
 
 impl Strategy1 {
-    pub fn new(value: &str) -> Result<Self, anyhow::Error> {
-        let mut iter = value.split_whitespace();
-        let opponent_choice =
-            TryInto::<Choice>::try_into(iter.next().context("missing opponent choice")?)?;
-        let player_choice =
-            TryInto::<Choice>::try_into(iter.next().context("missing player choice")?)?;
-
-        Ok(Self {
-            opponent_choice,
-            player_choice,
-        })
+    pub fn new(value: &str) -> ParseResult<Self> {
+        value.parse()
     }
 
     pub fn score(&self) -> u64 {
@@ -42,16 +35,14 @@ enum Choice {
     Scissors,
 }
 
-impl TryFrom<&str> for Choice {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "A" | "X" => Ok(Choice::Rock),
-            "B" | "Y" => Ok(Choice::Paper),
-            "C" | "Z" => Ok(Choice::Scissors),
-            _ => Err(anyhow::anyhow!("Invalid choice")),
-        }
+impl Parse for Choice {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        cursor.word_map("a choice (A, B, C, X, Y or Z)", |value| match value {
+            "A" | "X" => Some(Choice::Rock),
+            "B" | "Y" => Some(Choice::Paper),
+            "C" | "Z" => Some(Choice::Scissors),
+            _ => None,
+        })
     }
 }
 
@@ -76,4 +67,13 @@ pub mod tests {
         let game = Strategy1::new("C Z").unwrap();
         assert_eq!(game.score(), 6);
     }
+
+    #[test]
+    fn test_invalid_choice() {
+        let error = Strategy1::new("A W").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"column 3: expected a choice (A, B, C, X, Y or Z), found "W""#
+        );
+    }
 }
@@ -1,21 +1,15 @@
-use anyhow::Context;
+use parsing::{Cursor, Parse, ParseResult};
 
+#[derive(Parse)]
+#[aoc(format = "{} {}")]
 pub struct Strategy2 {
     opponent_choice: Choice,
     result: GameResult,
 }
 
 impl Strategy2 {
-    pub fn new(value: &str) -> Result<Self, anyhow::Error> {
-        let mut iter = value.split_whitespace();
-        let opponent_choice =
-            TryInto::<Choice>::try_into(iter.next().context("missing opponent choice")?)?;
-        let result = TryInto::<GameResult>::try_into(iter.next().context("missing game result")?)?;
-
-        Ok(Self {
-            opponent_choice,
-            result,
-        })
+    pub fn new(value: &str) -> ParseResult<Self> {
+        value.parse()
     }
 
     pub fn score(&self) -> u64 {
@@ -49,16 +43,14 @@ enum Choice {
     Scissors,
 }
 
-impl TryFrom<&str> for Choice {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "A" => Ok(Choice::Rock),
-            "B" => Ok(Choice::Paper),
-            "C" => Ok(Choice::Scissors),
-            _ => Err(anyhow::anyhow!("Invalid choice")),
-        }
+impl Parse for Choice {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        cursor.word_map("a choice (A, B or C)", |value| match value {
+            "A" => Some(Choice::Rock),
+            "B" => Some(Choice::Paper),
+            "C" => Some(Choice::Scissors),
+            _ => None,
+        })
     }
 }
 
@@ -70,16 +62,14 @@ enum GameResult {
     Win = 6,
 }
 
-impl TryFrom<&str> for GameResult {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "X" => Ok(GameResult::Loss),
-            "Y" => Ok(GameResult::Draw),
-            "Z" => Ok(GameResult::Win),
-            _ => Err(anyhow::anyhow!("Invalid game result")),
-        }
+impl Parse for GameResult {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        cursor.word_map("a game result (X, Y or Z)", |value| match value {
+            "X" => Some(GameResult::Loss),
+            "Y" => Some(GameResult::Draw),
+            "Z" => Some(GameResult::Win),
+            _ => None,
+        })
     }
 }
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use anyhow::Context;
 use lazy_static::lazy_static;
+use parsing::{Cursor, ParseResult};
 
 lazy_static! {
     pub static ref CHAR_SCORES: HashMap<char, u64> = {
@@ -19,16 +20,35 @@ lazy_static! {
     };
 }
 
+/// A rucksack is a non-empty run of letters.
+pub fn parse_rucksack(input: &str, line: usize) -> ParseResult<&str> {
+    let mut cursor = Cursor::at_line(input, line);
+
+    let items = cursor.take_while(|c| c.is_ascii_alphabetic());
+    cursor.end()?;
+
+    match items.is_empty() {
+        true => Err(cursor.error("an item")),
+        false => Ok(items),
+    }
+}
+
+#[derive(Default)]
 pub struct CompartmentParser {
     score: u64,
+    line: usize,
 }
 
 impl CompartmentParser {
     pub fn new() -> Self {
-        Self { score: 0 }
+        Self::default()
     }
 
-    pub fn parse(&mut self, input: &str) -> Result<(), anyhow::Error> {
+    /// Returns the item found in both compartments.
+    pub fn parse(&mut self, input: &str) -> Result<Option<char>, anyhow::Error> {
+        self.line += 1;
+        let input = parse_rucksack(input, self.line)?;
+
         let middle = input.chars().count() / 2;
 
         let iter_start = input.chars().take(middle);
@@ -39,11 +59,11 @@ impl CompartmentParser {
                 self.score += CHAR_SCORES
                     .get(&cs)
                     .context(format!("Missing score for char {cs}"))?;
-                break;
+                return Ok(Some(cs));
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
     pub fn get_score(&self) -> u64 {
@@ -77,4 +97,16 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 
         assert_eq!(parser.get_score(), 157);
     }
+
+    #[test]
+    fn test_invalid_item() {
+        let mut parser = CompartmentParser::new();
+        parser.parse("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        let error = parser.parse("jqHRNq1jqz").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 7: expected end of line, found "1jqz""#
+        );
+    }
 }
@@ -0,0 +1,74 @@
+pub mod compartment_parser;
+pub mod set_parser;
+
+use common::{Answers, Context};
+
+use crate::{
+    compartment_parser::{CompartmentParser, CHAR_SCORES},
+    set_parser::SetParser,
+};
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    let mut compartment_parser = CompartmentParser::new();
+    let mut set_parser = SetParser::new(context.config.get_at_least(2, "group_size", 3, 1)?);
+
+    let mut trace = context
+        .explain
+        .list(|| "item shared by both compartments and badge of each group".to_string());
+
+    for (i, line) in input.lines().enumerate() {
+        context.budget.check(|| format!("line {}", i + 1))?;
+
+        // both parsers reject the same rucksacks, so a skipped one leaves neither of them
+        // and the groups are made of the rucksacks around it
+        let shared = context
+            .diagnostics
+            .recover(compartment_parser.parse(line))?;
+        let badge = context.diagnostics.recover(set_parser.parse(line))?;
+
+        let (Some(shared), Some(badge)) = (shared, badge) else {
+            continue;
+        };
+
+        trace.entry(|| {
+            let mut entry = format!("rucksack {}: shared {}", i + 1, describe(shared));
+            if let Some(badge) = badge {
+                entry.push_str(&format!(", group badge {}", describe(Some(badge))));
+            }
+            entry
+        });
+    }
+
+    drop(trace);
+
+    context.diagnostics.recover(set_parser.finish())?;
+
+    Ok(Answers::new(
+        compartment_parser.get_score(),
+        set_parser.get_score(),
+    ))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    let mut compartment_parser = CompartmentParser::new();
+    let mut set_parser = SetParser::new(context.config.get_at_least(2, "group_size", 3, 1)?);
+
+    for (i, line) in input.lines().enumerate() {
+        context.budget.check(|| format!("line {}", i + 1))?;
+        context
+            .diagnostics
+            .recover(compartment_parser.parse(line))?;
+        context.diagnostics.recover(set_parser.parse(line))?;
+    }
+
+    context.diagnostics.recover(set_parser.finish())?;
+
+    Ok(())
+}
+
+fn describe(item: Option<char>) -> String {
+    item.map_or("nothing shared".to_string(), |item| {
+        format!("{item} ({} points)", CHAR_SCORES[&item])
+    })
+}
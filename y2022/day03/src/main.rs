@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/3
+fn main() -> anyhow::Result<()> {
+    common::run(2022, 3, y2022_day03::solve)
+}
@@ -0,0 +1,135 @@
+use anyhow::Context;
+use parsing::{ParseError, ParseResult};
+
+use crate::compartment_parser::{parse_rucksack, CHAR_SCORES};
+
+pub struct SetParser {
+    size: usize,
+    lines: Vec<String>,
+    score: u64,
+    line: usize,
+}
+
+impl SetParser {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            lines: Vec::with_capacity(size),
+            score: 0,
+            line: 0,
+        }
+    }
+
+    /// Returns the badge item once a whole set has been read.
+    pub fn parse(&mut self, input: &str) -> Result<Option<char>, anyhow::Error> {
+        self.line += 1;
+        self.lines
+            .push(parse_rucksack(input, self.line)?.to_string());
+
+        match self.lines.len() == self.size {
+            true => self.score_set(),
+            false => Ok(None),
+        }
+    }
+
+    fn score_set(&mut self) -> Result<Option<char>, anyhow::Error> {
+        // iterate through all items in the first backpack
+        let chars = self.lines[0].chars();
+        let mut badge = None;
+
+        for c in chars {
+            if self.lines.iter().skip(1).all(|value| value.contains(c)) {
+                // item is in all backpacks
+                self.score += CHAR_SCORES
+                    .get(&c)
+                    .context(format!("Missing score for char {c}"))?;
+                badge = Some(c);
+                break;
+            }
+        }
+
+        self.lines.clear();
+
+        Ok(badge)
+    }
+
+    /// Fails if the last group is missing rucksacks.
+    pub fn finish(&self) -> ParseResult<()> {
+        let missing = self.size.checked_sub(self.lines.len());
+
+        match (self.lines.len(), missing) {
+            (0, _) => Ok(()),
+            (_, Some(missing)) => Err(ParseError::new(
+                1,
+                format!("{missing} more rucksacks to complete the group"),
+                "end of input",
+            )
+            .at_line(self.line + 1)),
+            // a group of no rucksacks is never complete
+            (_, None) => Err(ParseError::new(
+                1,
+                "groups of at least one rucksack",
+                format!("groups of {}", self.size),
+            )
+            .at_line(self.line + 1)),
+        }
+    }
+
+    pub fn get_score(&self) -> u64 {
+        self.score
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::{BufRead, BufReader};
+
+    use super::*;
+
+    const INPUT: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+"#;
+
+    #[test]
+    fn it_works() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let mut parser = SetParser::new(3);
+
+        for line in reader.lines() {
+            let line = line.unwrap();
+            parser.parse(&line).unwrap();
+        }
+
+        assert_eq!(parser.get_score(), 70);
+        parser.finish().unwrap();
+    }
+
+    #[test]
+    fn test_incomplete_group() {
+        let mut parser = SetParser::new(3);
+
+        for line in INPUT.lines().take(4) {
+            parser.parse(line).unwrap();
+        }
+
+        assert_eq!(
+            parser.finish().unwrap_err().to_string(),
+            "line 5, column 1: expected 2 more rucksacks to complete the group, found end of input"
+        );
+    }
+
+    #[test]
+    fn test_empty_groups() {
+        let mut parser = SetParser::new(0);
+        parser.parse(INPUT.lines().next().unwrap()).unwrap();
+
+        assert_eq!(
+            parser.finish().unwrap_err().to_string(),
+            "line 2, column 1: expected groups of at least one rucksack, found groups of 0"
+        );
+    }
+}
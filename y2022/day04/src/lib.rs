@@ -0,0 +1,58 @@
+pub mod parser;
+
+use common::{Answers, Context};
+
+use crate::parser::Pair;
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    let mut count_part_1 = 0;
+    let mut count_part_2 = 0;
+
+    let mut trace = context
+        .explain
+        .list(|| "pairs counted by part 1 and part 2".to_string());
+
+    for line in parsing::lines(input.as_bytes()) {
+        let line = line?;
+        context.budget.check(|| format!("line {}", line.number))?;
+
+        let pair = Pair::new(&line.text).map_err(|e| e.at_line(line.number));
+
+        let Some(pair) = context.diagnostics.recover(pair)? else {
+            continue;
+        };
+
+        if pair.overlaps() {
+            count_part_2 += 1;
+
+            if pair.contains() {
+                count_part_1 += 1;
+            }
+
+            trace.entry(|| {
+                let relation = match pair.contains() {
+                    true => "one contains the other",
+                    false => "they overlap",
+                };
+                format!("line {} ({}): {relation}", line.number, line.text)
+            });
+        }
+    }
+
+    drop(trace);
+
+    Ok(Answers::new(count_part_1, count_part_2))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    for line in parsing::lines(input.as_bytes()) {
+        let line = line?;
+        context.budget.check(|| format!("line {}", line.number))?;
+        context
+            .diagnostics
+            .recover(Pair::new(&line.text).map_err(|e| e.at_line(line.number)))?;
+    }
+
+    Ok(())
+}
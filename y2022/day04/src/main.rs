@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/4
+fn main() -> anyhow::Result<()> {
+    common::run(2022, 4, y2022_day04::solve)
+}
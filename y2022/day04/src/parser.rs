@@ -1,20 +1,15 @@
-use anyhow::Context;
+use parsing::{Parse, ParseResult};
 
+#[derive(Parse)]
+#[aoc(format = "{},{}")]
 pub struct Pair {
     first: Assignment,
     second: Assignment,
 }
 
 impl Pair {
-    pub fn new(input: &str) -> Result<Self, anyhow::Error> {
-        let mut parts = input.split(',');
-        let first = parts.next().context("missing first assignment")?;
-        let second = parts.next().context("missing second assignment")?;
-
-        Ok(Self {
-            first: Assignment::new(first)?,
-            second: Assignment::new(second)?,
-        })
+    pub fn new(input: &str) -> ParseResult<Self> {
+        input.parse()
     }
 
     pub fn contains(&self) -> bool {
@@ -26,23 +21,14 @@ impl Pair {
     }
 }
 
+#[derive(Parse)]
+#[aoc(format = "{}-{}")]
 pub struct Assignment {
     start: u64,
     end: u64,
 }
 
 impl Assignment {
-    pub fn new(input: &str) -> Result<Self, anyhow::Error> {
-        let mut parts = input.split('-');
-        let start = parts.next().context("missing start")?;
-        let end = parts.next().context("missing end")?;
-
-        Ok(Self {
-            start: start.parse()?,
-            end: end.parse()?,
-        })
-    }
-
     pub fn contains(&self, other: &Self) -> bool {
         other.start >= self.start && other.end <= self.end
     }
@@ -158,4 +144,16 @@ pub mod tests {
 
         assert_eq!(count, 4);
     }
+
+    #[test]
+    fn test_invalid_pair() {
+        let error = Pair::new("2-4,6-8,1-2").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"column 8: expected end of line, found ",1-2""#
+        );
+
+        let error = Pair::new("2-4;6-8").err().unwrap();
+        assert_eq!(error.to_string(), r#"column 4: expected ",", found ";6-8""#);
+    }
 }
@@ -0,0 +1,527 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    io::{BufReader, Read},
+    marker::PhantomData,
+};
+
+use anyhow::Context;
+use common::{Budget, Diagnostics};
+use parsing::{Cursor, Line, Parse, ParseError, ParseResult};
+
+#[derive(Debug, PartialEq, Eq, Parse)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[aoc(format = "move {} from {} to {}")]
+pub struct Move {
+    amount: usize,
+    #[aoc(with = "parse_stack_index")]
+    from: usize,
+    #[aoc(with = "parse_stack_index")]
+    to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.amount,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+pub struct CrateMover9000;
+pub struct CrateMover9001;
+
+/// The same cranes, draining all crates of a move off the stack at once instead of one at a time.
+pub struct BulkCrateMover9000;
+pub struct BulkCrateMover9001;
+
+/// Stacks are serialized top first and moves with 0-based stack indexes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CrateMover<M> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    model: PhantomData<M>,
+    stacks: Vec<VecDeque<char>>,
+    moves: VecDeque<Move>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    moved: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    budget: Budget,
+}
+
+impl<M> CrateMover<M> {
+    pub fn new<R: Read>(buffer: BufReader<R>) -> anyhow::Result<Self> {
+        Self::parse(buffer, &Diagnostics::strict())
+    }
+
+    /// Reads the drawing and the procedure, skipping bad rows, bad moves and blocks after the procedure
+    /// in lenient mode.
+    /// The stack labels and the blank line after them are required in either mode.
+    pub fn parse<R: Read>(buffer: BufReader<R>, diagnostics: &Diagnostics) -> anyhow::Result<Self> {
+        let mut blocks = parsing::blocks(buffer);
+
+        let drawing = blocks.next().context("No lines in input")??;
+        let (labels, rows) = drawing.lines.split_last().context("No lines in input")?;
+
+        // the last line of the drawing numbers the stacks
+        let stacks_count = parsing::columns(&mut labels.cursor(), 3, " ", |cell| {
+            cell.take_while(|c| c == ' ');
+            let label = cell.integer::<usize>()?;
+            cell.take_while(|c| c == ' ');
+
+            Ok(label)
+        })?
+        .len();
+
+        let mut stacks = Vec::with_capacity(stacks_count);
+
+        for _ in 0..stacks_count {
+            stacks.push(VecDeque::new());
+        }
+
+        for row in rows {
+            let crates = diagnostics.recover(parse_row(row, stacks_count))?;
+
+            for (i, c) in crates.into_iter().flatten().enumerate() {
+                if let Some(c) = c {
+                    stacks[i].push_back(c);
+                }
+            }
+        }
+
+        let mut moves = VecDeque::new();
+
+        if let Some(procedure) = blocks.next() {
+            for line in procedure?.lines {
+                moves.extend(diagnostics.recover(parse_move(&line, stacks_count))?);
+            }
+        }
+
+        // nothing comes after the procedure
+        for block in blocks {
+            let block = block?;
+            diagnostics.recover(Err::<(), _>(
+                ParseError::new(1, "end of input", format!("{:?}", block.lines[0].text))
+                    .at_line(block.first_line()),
+            ))?;
+        }
+
+        Ok(Self {
+            model: PhantomData,
+            stacks,
+            moves,
+            moved: 0,
+            budget: Budget::unlimited(),
+        })
+    }
+
+    /// Stops moving crates once the budget is spent.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+}
+
+fn parse_row(row: &Line, stacks_count: usize) -> ParseResult<Vec<Option<char>>> {
+    let mut cursor = row.cursor();
+    let crates = parsing::columns(&mut cursor, 3, " ", parse_crate)?;
+
+    match crates.len() > stacks_count {
+        true => Err(cursor.error(format!("at most {stacks_count} stacks"))),
+        false => Ok(crates),
+    }
+}
+
+/// A cell of the drawing is either `[X]` or blank.
+fn parse_crate(cell: &mut Cursor<'_>) -> ParseResult<Option<char>> {
+    if cell.peek() == Some(' ') {
+        cell.literal("   ")?;
+        return Ok(None);
+    }
+
+    cell.literal("[")?;
+    let c = cell.char()?;
+    cell.literal("]")?;
+
+    Ok(Some(c))
+}
+
+/// Stacks are numbered from 1 in the input but indexed from 0 here.
+fn parse_stack_index(cursor: &mut Cursor<'_>) -> ParseResult<usize> {
+    let start = cursor.clone();
+
+    match cursor.integer::<usize>()? {
+        0 => Err(start.error("a stack number starting at 1")),
+        number => Ok(number - 1),
+    }
+}
+
+/// A move of the procedure, which can only name stacks of the drawing.
+fn parse_move(line: &Line, stacks_count: usize) -> ParseResult<Move> {
+    let m = line.parse::<Move>()?;
+
+    // the stack numbers are the fourth and sixth words of `move {} from {} to {}`
+    let mut column = 1;
+    for (i, word) in line.text.split(' ').enumerate() {
+        let stack = match i {
+            3 => m.from,
+            5 => m.to,
+            _ => 0,
+        };
+
+        if stack >= stacks_count {
+            return Err(ParseError::new(
+                column,
+                format!("a stack number from 1 to {stacks_count}"),
+                format!("{word:?}"),
+            )
+            .at_line(line.number));
+        }
+        column += word.len() + 1;
+    }
+
+    Ok(m)
+}
+
+/// How a crane model carries out a single move.
+pub trait Model {
+    fn carry(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<()>;
+}
+
+impl Model for CrateMover9000 {
+    fn carry(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<()> {
+        for i in 0..m.amount {
+            budget.check(|| format!("{m}, {i} of {} crates moved", m.amount))?;
+
+            let c = stacks[m.from].pop_front().context("No crates to move")?;
+
+            stacks[m.to].push_front(c);
+        }
+
+        Ok(())
+    }
+}
+
+impl Model for CrateMover9001 {
+    fn carry(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<()> {
+        let mut buffer = Vec::with_capacity(m.amount);
+
+        for i in 0..m.amount {
+            budget.check(|| format!("{m}, {i} of {} crates picked up", m.amount))?;
+
+            let c = stacks[m.from].pop_front().context("No crates to move")?;
+
+            buffer.push(c);
+        }
+
+        while let Some(c) = buffer.pop() {
+            stacks[m.to].push_front(c);
+        }
+
+        Ok(())
+    }
+}
+
+/// Takes the crates of a move off the top of its stack, the top one first.
+fn drain(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<Vec<char>> {
+    budget.check(|| m.to_string())?;
+
+    if stacks[m.from].len() < m.amount {
+        anyhow::bail!("No crates to move");
+    }
+
+    Ok(stacks[m.from].drain(..m.amount).collect())
+}
+
+impl Model for BulkCrateMover9000 {
+    fn carry(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<()> {
+        // one crate at a time puts the top one at the bottom
+        for c in drain(stacks, m, budget)? {
+            stacks[m.to].push_front(c);
+        }
+
+        Ok(())
+    }
+}
+
+impl Model for BulkCrateMover9001 {
+    fn carry(stacks: &mut [VecDeque<char>], m: &Move, budget: &Budget) -> anyhow::Result<()> {
+        for c in drain(stacks, m, budget)?.into_iter().rev() {
+            stacks[m.to].push_front(c);
+        }
+
+        Ok(())
+    }
+}
+
+impl<M: Model> CrateMover<M> {
+    pub fn move_crates(&mut self) -> anyhow::Result<()> {
+        while self.move_next()?.is_some() {}
+
+        Ok(())
+    }
+
+    /// Carries out the next move of the procedure and returns it.
+    pub fn move_next(&mut self) -> anyhow::Result<Option<Move>> {
+        let Some(m) = self.moves.pop_front() else {
+            return Ok(None);
+        };
+
+        self.moved += 1;
+        M::carry(&mut self.stacks, &m, &self.budget)
+            .with_context(|| format!("move {} of the procedure", self.moved))?;
+
+        Ok(Some(m))
+    }
+
+    /// The crates of every stack, top first.
+    pub fn describe_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| format!("{}:{}", i + 1, stack.iter().collect::<String>()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl<M> Display for CrateMover<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for stack in &self.stacks {
+            let c = stack.iter().next().unwrap_or(&' ');
+            write!(f, "{c}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    const INPUT: &str = r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+"#;
+
+    #[test]
+    fn test_input_parsing() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let crane = CrateMover::<CrateMover9000>::new(reader).unwrap();
+
+        assert_eq!(crane.stacks.len(), 3);
+
+        let mut stack_0 = crane.stacks[0].iter();
+        assert_eq!(stack_0.next(), Some(&'N'));
+        assert_eq!(stack_0.next(), Some(&'Z'));
+        assert_eq!(stack_0.next(), None);
+
+        let mut stack_1 = crane.stacks[1].iter();
+        assert_eq!(stack_1.next(), Some(&'D'));
+        assert_eq!(stack_1.next(), Some(&'C'));
+        assert_eq!(stack_1.next(), Some(&'M'));
+        assert_eq!(stack_1.next(), None);
+
+        let mut stack_2 = crane.stacks[2].iter();
+        assert_eq!(stack_2.next(), Some(&'P'));
+        assert_eq!(stack_2.next(), None);
+
+        assert_eq!(crane.moves.len(), 4);
+        assert_eq!(
+            crane.moves[0],
+            Move {
+                amount: 1,
+                from: 1,
+                to: 0
+            }
+        );
+        assert_eq!(
+            crane.moves[1],
+            Move {
+                amount: 3,
+                from: 0,
+                to: 2
+            }
+        );
+        assert_eq!(
+            crane.moves[2],
+            Move {
+                amount: 2,
+                from: 1,
+                to: 0
+            }
+        );
+        assert_eq!(
+            crane.moves[3],
+            Move {
+                amount: 1,
+                from: 0,
+                to: 1
+            }
+        );
+
+        assert_eq!(crane.to_string(), "NDP");
+        assert_eq!(crane.moves[2].to_string(), "move 2 from 2 to 1");
+    }
+
+    #[test]
+    fn test_crate_mover_9000() {
+        let reader = BufReader::new(INPUT.as_bytes());
+
+        let mut crane = CrateMover::<CrateMover9000>::new(reader).unwrap();
+        crane.move_crates().unwrap();
+
+        assert_eq!(crane.to_string(), "CMZ");
+    }
+
+    #[test]
+    fn test_move_next() {
+        let reader = BufReader::new(INPUT.as_bytes());
+
+        let mut crane = CrateMover::<CrateMover9001>::new(reader).unwrap();
+        assert_eq!(crane.describe_stacks(), "1:NZ 2:DCM 3:P");
+
+        let m = crane.move_next().unwrap().unwrap();
+        assert_eq!(m.to_string(), "move 1 from 2 to 1");
+        assert_eq!(crane.describe_stacks(), "1:DNZ 2:CM 3:P");
+
+        crane.move_crates().unwrap();
+        assert!(crane.move_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_crate_mover_9001() {
+        let reader = BufReader::new(INPUT.as_bytes());
+
+        let mut crane = CrateMover::<CrateMover9001>::new(reader).unwrap();
+        crane.move_crates().unwrap();
+
+        assert_eq!(crane.to_string(), "MCD");
+    }
+
+    #[test]
+    fn test_bulk_crate_movers() {
+        let mut crane =
+            CrateMover::<BulkCrateMover9000>::new(BufReader::new(INPUT.as_bytes())).unwrap();
+        crane.move_crates().unwrap();
+        assert_eq!(crane.to_string(), "CMZ");
+
+        let mut crane =
+            CrateMover::<BulkCrateMover9001>::new(BufReader::new(INPUT.as_bytes())).unwrap();
+        crane.move_crates().unwrap();
+        assert_eq!(crane.to_string(), "MCD");
+
+        let input = INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let mut crane =
+            CrateMover::<BulkCrateMover9000>::new(BufReader::new(input.as_bytes())).unwrap();
+        let error = crane.move_crates().unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "move 2 of the procedure: No crates to move"
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let reader = BufReader::new(INPUT.as_bytes());
+
+        let budget = Budget::unlimited();
+        let mut crane = CrateMover::<CrateMover9000>::new(reader)
+            .unwrap()
+            .with_budget(budget.clone());
+
+        crane.move_next().unwrap();
+        budget.cancel();
+
+        let error = crane.move_crates().unwrap_err();
+        assert_eq!(error.to_string(), "move 2 of the procedure");
+        assert!(
+            format!("{error:#}").ends_with("stopped at move 3 from 1 to 3, 0 of 3 crates moved")
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = INPUT
+            .replace("[Z] [M] [P]", "[Z] [M] [P] [Q]")
+            .replace("move 2 from 2 to 1", "move 2 from 2 onto 1");
+        let reader = BufReader::new(input.as_bytes());
+
+        let diagnostics = Diagnostics::lenient();
+        let crane = CrateMover::<CrateMover9000>::parse(reader, &diagnostics).unwrap();
+
+        assert_eq!(crane.describe_stacks(), "1:N 2:DC 3:");
+        assert_eq!(crane.moves.len(), 3);
+
+        let warnings = diagnostics.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].line(), Some(3));
+        assert_eq!(warnings[1].line(), Some(8));
+    }
+
+    #[test]
+    fn test_unknown_stack() {
+        let input = INPUT.replace("move 2 from 2 to 1", "move 2 from 2 to 7");
+
+        let error = CrateMover::<CrateMover9000>::new(BufReader::new(input.as_bytes()))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 8, column 18: expected a stack number from 1 to 3, found "7""#
+        );
+
+        let diagnostics = Diagnostics::lenient();
+        let crane =
+            CrateMover::<CrateMover9000>::parse(BufReader::new(input.as_bytes()), &diagnostics)
+                .unwrap();
+
+        assert_eq!(crane.moves.len(), 3);
+        assert_eq!(diagnostics.warnings()[0].line(), Some(8));
+    }
+
+    #[test]
+    fn test_trailing_block() {
+        let input = format!("{INPUT}\nmove 1 from 1 to 2\n");
+
+        let error = CrateMover::<CrateMover9000>::new(BufReader::new(input.as_bytes()))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 11, column 1: expected end of input, found "move 1 from 1 to 2""#
+        );
+
+        let diagnostics = Diagnostics::lenient();
+        let crane =
+            CrateMover::<CrateMover9000>::parse(BufReader::new(input.as_bytes()), &diagnostics)
+                .unwrap();
+
+        assert_eq!(crane.moves.len(), 4);
+        assert_eq!(diagnostics.warnings()[0].line(), Some(11));
+    }
+
+    #[test]
+    fn test_invalid_move() {
+        let input = INPUT.replace("move 2 from 2 to 1", "move 2 from 2 onto 1");
+        let reader = BufReader::new(input.as_bytes());
+
+        let error = CrateMover::<CrateMover9000>::new(reader).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 8, column 14: expected " to ", found " onto 1""#
+        );
+    }
+}
@@ -0,0 +1,71 @@
+pub mod crate_mover;
+
+use std::io::BufReader;
+
+use common::{Answers, Context, Diagnostics, Explain, Implementation};
+
+use crate::crate_mover::{
+    BulkCrateMover9000, BulkCrateMover9001, CrateMover, CrateMover9000, CrateMover9001, Model,
+};
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::new("per-crate", solve),
+    Implementation::new("bulk", solve_with::<BulkCrateMover9000, BulkCrateMover9001>),
+];
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    solve_with::<CrateMover9000, CrateMover9001>(context, input)
+}
+
+/// Solves part 1 with the `Part1` crane and part 2 with the `Part2` one.
+fn solve_with<Part1: Model, Part2: Model>(
+    context: &Context,
+    input: &str,
+) -> anyhow::Result<Answers> {
+    let mut crane =
+        CrateMover::<Part1>::parse(BufReader::new(input.as_bytes()), &context.diagnostics)?
+            .with_budget(context.budget.clone());
+    explain_moves(&context.explain, "part 1: CrateMover 9000", &mut crane)?;
+    crane.move_crates()?;
+
+    let part1 = crane.to_string();
+
+    // both cranes read the same input, the bad records are only worth reporting once
+    let mut crane = CrateMover::<Part2>::parse(
+        BufReader::new(input.as_bytes()),
+        &Diagnostics::new(context.diagnostics.mode()),
+    )?
+    .with_budget(context.budget.clone());
+    explain_moves(&context.explain, "part 2: CrateMover 9001", &mut crane)?;
+    crane.move_crates()?;
+
+    Ok(Answers::new(part1, crane))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    CrateMover::<CrateMover9000>::parse(BufReader::new(input.as_bytes()), &context.diagnostics)?;
+
+    Ok(())
+}
+
+/// Steps through the moves one at a time, but only when there's someone to tell about them.
+fn explain_moves<M: Model>(
+    explain: &Explain,
+    title: &str,
+    crane: &mut CrateMover<M>,
+) -> anyhow::Result<()> {
+    if !explain.is_enabled() {
+        return Ok(());
+    }
+
+    explain.note(|| format!("{title} starts with {}", crane.describe_stacks()));
+
+    let mut trace = explain.list(|| format!("{title} moves, stacks listed top first"));
+
+    while let Some(m) = crane.move_next()? {
+        trace.entry(|| format!("{m}: {}", crane.describe_stacks()));
+    }
+
+    Ok(())
+}
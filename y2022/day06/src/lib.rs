@@ -0,0 +1,76 @@
+pub mod parser;
+
+use common::{Answers, Context, Implementation};
+
+use crate::parser::{Parser, Window};
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::new("hash-map", solve),
+    Implementation::new("counts", |context, input| {
+        solve_with(context, input, Window::Counts)
+    }),
+];
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    solve_with(context, input, Window::HashMap)
+}
+
+fn solve_with(context: &Context, input: &str, window: Window) -> anyhow::Result<Answers> {
+    let marker_length_1 = context.config.get_at_least(1, "marker_length", 4, 1)?;
+    let marker_length_2 = context.config.get_at_least(2, "marker_length", 14, 1)?;
+
+    let mut parser_part_1 = Parser::new(marker_length_1)
+        .with_window(window)
+        .with_budget(context.budget.clone());
+    let mut parser_part_2 = Parser::new(marker_length_2)
+        .with_window(window)
+        .with_budget(context.budget.clone());
+
+    let mut trace = context
+        .explain
+        .list(|| "markers found in each datastream".to_string());
+
+    for (i, line) in input.lines().enumerate() {
+        let end_1 = context.diagnostics.recover(parser_part_1.parse(line))?;
+        let end_2 = context.diagnostics.recover(parser_part_2.parse(line))?;
+
+        let (Some(end_1), Some(end_2)) = (end_1, end_2) else {
+            continue;
+        };
+
+        trace.entry(|| {
+            format!(
+                "line {}: {}, {}",
+                i + 1,
+                describe_marker(line, marker_length_1, end_1),
+                describe_marker(line, marker_length_2, end_2)
+            )
+        });
+    }
+
+    drop(trace);
+
+    Ok(Answers::new(
+        parser_part_1.get_total(),
+        parser_part_2.get_total(),
+    ))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    let mut parser = Parser::new(context.config.get_at_least(1, "marker_length", 4, 1)?)
+        .with_budget(context.budget.clone());
+
+    for line in input.lines() {
+        context.diagnostics.recover(parser.parse(line))?;
+    }
+
+    Ok(())
+}
+
+fn describe_marker(line: &str, marker_length: usize, end: usize) -> String {
+    match line.get(end.saturating_sub(marker_length)..end) {
+        Some(marker) => format!("{marker} ends at character {end}"),
+        None => format!("no marker of {marker_length} different characters"),
+    }
+}
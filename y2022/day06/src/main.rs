@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/6
+fn main() -> anyhow::Result<()> {
+    common::run_implementations(2022, 6, y2022_day06::IMPLEMENTATIONS)
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use common::Budget;
+use parsing::Cursor;
+
+/// How the last characters of the datastream are kept track of.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// the last position of every character, pruned as the window slides
+    #[default]
+    HashMap,
+    /// how often each letter appears in the window and how many of them are distinct
+    Counts,
+}
+
+pub struct Parser {
+    marker_length: usize,
+    window: Window,
+    total: usize,
+    line: usize,
+    budget: Budget,
+}
+
+impl Parser {
+    pub fn new(marker_length: usize) -> Self {
+        Parser {
+            marker_length,
+            window: Window::default(),
+            total: 0,
+            line: 0,
+            budget: Budget::unlimited(),
+        }
+    }
+
+    /// Stops scanning the datastream once the budget is spent.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn parse(&mut self, input: &str) -> anyhow::Result<usize> {
+        self.line += 1;
+
+        let mut cursor = Cursor::at_line(input, self.line);
+        let datastream = cursor.take_while(|c| c.is_ascii_lowercase());
+        cursor.end()?;
+
+        let end = match self.window {
+            Window::HashMap => self.find_with_hash_map(datastream)?,
+            Window::Counts => self.find_with_counts(datastream)?,
+        };
+
+        if let Some(end) = end {
+            self.total += end;
+        }
+
+        Ok(end.unwrap_or(usize::MAX))
+    }
+
+    fn find_with_hash_map(&self, datastream: &str) -> anyhow::Result<Option<usize>> {
+        let mut memory = HashMap::new();
+
+        for (i, c) in datastream.chars().enumerate() {
+            self.budget
+                .check(|| format!("line {}, character {}", self.line, i + 1))?;
+
+            memory.insert(c, i);
+
+            if memory.len() >= self.marker_length {
+                let index_to_keep = i + 1 - self.marker_length;
+                memory.retain(|_, ri| *ri >= index_to_keep);
+            }
+
+            if memory.len() >= self.marker_length {
+                return Ok(Some(i + 1));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn find_with_counts(&self, datastream: &str) -> anyhow::Result<Option<usize>> {
+        let letters = datastream.as_bytes();
+        let mut counts = [0usize; 26];
+        let mut distinct = 0;
+
+        for (i, c) in letters.iter().enumerate() {
+            self.budget
+                .check(|| format!("line {}, character {}", self.line, i + 1))?;
+
+            counts[(c - b'a') as usize] += 1;
+            if counts[(c - b'a') as usize] == 1 {
+                distinct += 1;
+            }
+
+            // the letter sliding out of the window
+            if i >= self.marker_length {
+                let old = (letters[i - self.marker_length] - b'a') as usize;
+                counts[old] -= 1;
+                if counts[old] == 0 {
+                    distinct -= 1;
+                }
+            }
+
+            if distinct >= self.marker_length {
+                return Ok(Some(i + 1));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.total
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let mut parser = Parser::new(4);
+
+        assert_eq!(parser.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 7);
+        assert_eq!(parser.parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
+        assert_eq!(parser.parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 6);
+        assert_eq!(
+            parser.parse("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(),
+            10
+        );
+        assert_eq!(
+            parser.parse("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(),
+            11
+        );
+
+        assert_eq!(parser.get_total(), 39);
+    }
+
+    #[test]
+    fn test_windows_agree() {
+        for datastream in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "aaaaaaaaaaaaaaab",
+            "",
+        ] {
+            for marker_length in [1, 4, 14] {
+                let mut hash_map = Parser::new(marker_length);
+                let mut counts = Parser::new(marker_length).with_window(Window::Counts);
+
+                assert_eq!(
+                    hash_map.parse(datastream).unwrap(),
+                    counts.parse(datastream).unwrap(),
+                    "{datastream} with markers of {marker_length}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parser_error_position() {
+        let mut parser = Parser::new(4);
+
+        let error = parser.parse("mjqjpq mgbljsphdztnvjfqwrcgsmlb").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 1, column 7: expected end of line, found " mgbljsphdztnvjf""#
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let budget = Budget::unlimited();
+        let mut parser = Parser::new(4).with_budget(budget.clone());
+
+        assert_eq!(parser.parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
+        budget.cancel();
+
+        let error = parser.parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("stopped at line 2, character 1"));
+        assert_eq!(parser.get_total(), 5);
+    }
+}
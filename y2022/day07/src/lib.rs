@@ -0,0 +1,60 @@
+pub mod parser;
+
+use std::io::BufReader;
+
+use common::{Answers, Context};
+
+use crate::parser::Parser;
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    let parser = Parser::default()
+        .with_budget(context.budget.clone())
+        .read_from(BufReader::new(input.as_bytes()), &context.diagnostics)?;
+
+    let config = &context.config;
+    let explain = &context.explain;
+
+    let top_limit: usize = config.get(1, "top_limit", 100000)?;
+    let total_disk_space = config.get_at_least(2, "total_disk_space", 70000000, 1)?;
+    let free_space_required: usize = config.get(2, "free_space_required", 30000000)?;
+
+    if explain.is_enabled() {
+        let mut trace = explain.list(|| format!("part 1: directories of at most {top_limit}"));
+        for (path, size) in parser.get_directories_by_top_limit(top_limit) {
+            trace.entry(|| format!("{path}: {size}"));
+        }
+        drop(trace);
+
+        let used_space = parser.get_used_space()?;
+        let free_space = total_disk_space.saturating_sub(used_space);
+        explain.note(|| {
+            format!(
+                "part 2: {used_space} of {total_disk_space} used, {free_space} free, {free_space_required} required"
+            )
+        });
+
+        match parser.get_directory_to_delete(total_disk_space, free_space_required)? {
+            Some((path, size)) => explain.note(|| {
+                format!(
+                    "part 2: {path} ({size}) is the smallest directory freeing at least {}",
+                    free_space_required - free_space
+                )
+            }),
+            None => explain.note(|| "part 2: there's enough free space already".to_string()),
+        }
+    }
+
+    Ok(Answers::new(
+        parser.get_all_by_top_limit(top_limit),
+        parser.get_one_by_free_space_required(total_disk_space, free_space_required)?,
+    ))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    Parser::default()
+        .with_budget(context.budget.clone())
+        .read_from(BufReader::new(input.as_bytes()), &context.diagnostics)?;
+
+    Ok(())
+}
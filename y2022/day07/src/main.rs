@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/7
+fn main() -> anyhow::Result<()> {
+    common::run(2022, 7, y2022_day07::solve)
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufReader, Read},
+};
+
+use anyhow::Context;
+use common::{Budget, Diagnostics};
+use parsing::{Cursor, Parse, ParseResult};
+
+/// A single line of the terminal transcript.
+enum TerminalLine {
+    ChangeDirectory(String),
+    List,
+    Directory,
+    File(usize),
+}
+
+impl Parse for TerminalLine {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        if cursor.peek() == Some('$') {
+            // we're parsing a command
+            cursor.literal("$ ")?;
+
+            let command = cursor.word_map("a cd or ls command", |command| match command {
+                "cd" | "ls" => Some(command),
+                _ => None,
+            })?;
+
+            if command == "ls" {
+                return Ok(TerminalLine::List);
+            }
+
+            cursor.literal(" ")?;
+            let dir = cursor.word()?;
+
+            return Ok(TerminalLine::ChangeDirectory(dir.to_string()));
+        }
+
+        // we're parsing a file or directory
+        if cursor.rest().starts_with("dir ") {
+            cursor.literal("dir ")?;
+            cursor.word()?;
+            return Ok(TerminalLine::Directory);
+        }
+
+        let size = cursor.integer()?;
+        cursor.literal(" ")?;
+        cursor.word()?;
+
+        Ok(TerminalLine::File(size))
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Parser {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_directory_sizes"))]
+    directory_sizes: BTreeMap<String, usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    budget: Budget,
+}
+
+impl Parser {
+    pub fn new<R: Read>(buffer: BufReader<R>) -> anyhow::Result<Self> {
+        Self::parse(buffer, &Diagnostics::strict())
+    }
+
+    /// Replays the transcript, skipping lines that can't be understood in lenient mode.
+    pub fn parse<R: Read>(buffer: BufReader<R>, diagnostics: &Diagnostics) -> anyhow::Result<Self> {
+        Self::default().read_from(buffer, diagnostics)
+    }
+
+    /// Stops replaying the transcript once the budget is spent.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Replays a transcript with the budget of the parser, as `parse` does.
+    pub fn read_from<R: Read>(
+        mut self,
+        buffer: BufReader<R>,
+        diagnostics: &Diagnostics,
+    ) -> anyhow::Result<Self> {
+        let mut path = Vec::new();
+        let directory_sizes = &mut self.directory_sizes;
+
+        for line in parsing::lines(buffer) {
+            let line = line?;
+            self.budget.check(|| format!("line {}", line.number))?;
+
+            let Some(terminal_line) = diagnostics.recover(line.parse::<TerminalLine>())? else {
+                continue;
+            };
+
+            match terminal_line {
+                TerminalLine::ChangeDirectory(dir) if dir == ".." => {
+                    // we're going up a directory
+                    path.pop();
+                }
+                TerminalLine::ChangeDirectory(dir) => {
+                    path.push(dir);
+                }
+                TerminalLine::List => {}
+                TerminalLine::Directory => {
+                    // this is a directory - we can ignore it
+                }
+                TerminalLine::File(size) => {
+                    // we need to add the size to all parent directories
+                    // computing each parent directory on the fly is a bit wasteful
+                    // but it's fast enough for this problem
+                    for i in 0..path.len() {
+                        self.budget.check(|| {
+                            format!(
+                                "line {}, directory {} of {}",
+                                line.number,
+                                i + 1,
+                                path.len()
+                            )
+                        })?;
+
+                        let path_str = path[..=i].join("/");
+                        let entry = directory_sizes.entry(path_str).or_insert(0);
+                        *entry += size;
+                    }
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn get_all_by_top_limit(&self, top_limit: usize) -> usize {
+        self.directory_sizes
+            .values()
+            .filter(|value| value <= &&top_limit)
+            .sum()
+    }
+
+    /// The directories counted by `get_all_by_top_limit`, largest first.
+    pub fn get_directories_by_top_limit(&self, top_limit: usize) -> Vec<(String, usize)> {
+        let mut directories = self
+            .directory_sizes
+            .iter()
+            .filter(|(_, size)| size <= &&top_limit)
+            .map(|(path, size)| (display_path(path), *size))
+            .collect::<Vec<_>>();
+
+        directories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        directories
+    }
+
+    /// The directory picked by `get_one_by_free_space_required`, if one has to be deleted at all.
+    pub fn get_directory_to_delete(
+        &self,
+        total_disk_space: usize,
+        free_space_required: usize,
+    ) -> anyhow::Result<Option<(String, usize)>> {
+        let size = self.get_one_by_free_space_required(total_disk_space, free_space_required)?;
+
+        if size == 0 {
+            return Ok(None);
+        }
+
+        let path = self
+            .directory_sizes
+            .iter()
+            .filter(|(_, value)| **value == size)
+            .map(|(path, _)| display_path(path))
+            .min()
+            .context("failed to find a directory large enough")?;
+
+        Ok(Some((path, size)))
+    }
+
+    pub fn get_used_space(&self) -> anyhow::Result<usize> {
+        self.directory_sizes
+            .get("/")
+            .copied()
+            .context("failed to find the root directory")
+    }
+
+    pub fn get_one_by_free_space_required(
+        &self,
+        total_disk_space: usize,
+        free_space_required: usize,
+    ) -> anyhow::Result<usize> {
+        let current_space_usage = self.get_used_space()?;
+
+        let current_free_space =
+            total_disk_space
+                .checked_sub(current_space_usage)
+                .context(format!(
+                    "{current_space_usage} used doesn't fit on a disk of {total_disk_space}"
+                ))?;
+
+        if current_free_space >= free_space_required {
+            return Ok(0);
+        }
+
+        let extra_free_space_required = free_space_required - current_free_space;
+
+        let mut values = self
+            .directory_sizes
+            .values()
+            .filter(|value| value >= &&extra_free_space_required)
+            .collect::<Vec<_>>();
+
+        values.sort();
+        let value = **values
+            .first()
+            .context("failed to find a directory large enough")?;
+
+        Ok(value)
+    }
+}
+
+/// Directory paths are joined with `/` under the root `/`, which would print as `//a/e`.
+fn display_path(path: &str) -> String {
+    match path.strip_prefix("//") {
+        Some(rest) => format!("/{rest}"),
+        None => path.to_string(),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_directory_sizes<S: serde::Serializer>(
+    directory_sizes: &BTreeMap<String, usize>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        directory_sizes
+            .iter()
+            .map(|(path, size)| (display_path(path), size)),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+"#;
+
+    #[test]
+    fn test_parser_get_all_by_top_limit() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let parser = Parser::new(reader).unwrap();
+
+        assert_eq!(parser.get_all_by_top_limit(100000), 95437);
+        assert_eq!(
+            parser.get_directories_by_top_limit(100000),
+            vec![("/a".to_string(), 94853), ("/a/e".to_string(), 584)]
+        );
+    }
+
+    #[test]
+    fn test_parser_get_one_by_free_space_required() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let parser = Parser::new(reader).unwrap();
+
+        assert_eq!(
+            parser
+                .get_one_by_free_space_required(70000000, 30000000)
+                .unwrap(),
+            24933642
+        );
+        assert_eq!(
+            parser.get_directory_to_delete(70000000, 30000000).unwrap(),
+            Some(("/d".to_string(), 24933642))
+        );
+        assert_eq!(
+            parser.get_directory_to_delete(70000000, 10000).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_disk_too_small() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let parser = Parser::new(reader).unwrap();
+
+        assert_eq!(
+            parser
+                .get_one_by_free_space_required(1000, 30000000)
+                .unwrap_err()
+                .to_string(),
+            "48381165 used doesn't fit on a disk of 1000"
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let budget = Budget::unlimited();
+        budget.cancel();
+
+        let error = Parser::default()
+            .with_budget(budget)
+            .read_from(BufReader::new(INPUT.as_bytes()), &Diagnostics::strict())
+            .err()
+            .unwrap();
+        assert!(error.to_string().ends_with("stopped at line 1"), "{error}");
+    }
+
+    #[test]
+    fn test_parser_error_position() {
+        let input = INPUT.replace("62596 h.lst", "62596h.lst");
+        let reader = BufReader::new(input.as_bytes());
+
+        let error = Parser::new(reader).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 12, column 6: expected " ", found "h.lst""#
+        );
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let input = INPUT.replace("$ cd e\n", "$ cd e\n$ rm -rf k\n");
+
+        let error = Parser::new(BufReader::new(input.as_bytes())).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 14, column 3: expected a cd or ls command, found "rm -rf k""#
+        );
+
+        let diagnostics = Diagnostics::lenient();
+        let parser = Parser::parse(BufReader::new(input.as_bytes()), &diagnostics).unwrap();
+
+        assert_eq!(parser.get_all_by_top_limit(100000), 95437);
+        assert_eq!(diagnostics.warnings().len(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+pub mod parser;
+
+use std::io::BufReader;
+
+use common::{Answers, Context, Implementation};
+
+use crate::parser::Parser;
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::new("scan", solve),
+    Implementation::new("stacks", |context, input| {
+        solve_with(context, input, Parser::evaluate_with_stacks)
+    }),
+];
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    solve_with(context, input, Parser::evaluate)
+}
+
+fn solve_with(
+    context: &Context,
+    input: &str,
+    evaluate: fn(&Parser) -> anyhow::Result<(usize, u32)>,
+) -> anyhow::Result<Answers> {
+    let explain = &context.explain;
+
+    let parser = Parser::parse(BufReader::new(input.as_bytes()), &context.diagnostics)?
+        .with_budget(context.budget.clone());
+
+    let (visible_trees, max_scenic_score) = evaluate(&parser)?;
+
+    if explain.is_enabled() {
+        let (rows, columns) = parser.dimensions();
+        let edges = parser.edges();
+        explain.note(|| {
+            format!(
+                "part 1: the grid is {rows}x{columns}, all {edges} trees on the edges are visible and {} inside",
+                visible_trees - edges
+            )
+        });
+
+        if let Some((row, column, [top, bottom, left, right])) = parser.best_scenic_tree()? {
+            explain.note(|| {
+                format!(
+                    "part 2: the tree at row {}, column {} sees {top} up, {bottom} down, {left} left and {right} right",
+                    row + 1,
+                    column + 1
+                )
+            });
+        }
+    }
+
+    Ok(Answers::new(visible_trees, max_scenic_score))
+}
+
+/// Runs the parsers over the input without computing the answers.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    Parser::parse(BufReader::new(input.as_bytes()), &context.diagnostics)?;
+
+    Ok(())
+}
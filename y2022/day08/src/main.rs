@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/8
+fn main() -> anyhow::Result<()> {
+    common::run_implementations(2022, 8, y2022_day08::IMPLEMENTATIONS)
+}
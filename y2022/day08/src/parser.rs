@@ -0,0 +1,438 @@
+use std::io::{BufReader, Read};
+
+use anyhow::Context;
+use common::{Budget, Diagnostics};
+use parsing::{Line, ParseResult};
+
+/// Trees are serialized row by row as a flat list of heights.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Parser {
+    rows: usize,
+    columns: usize,
+    trees: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    budget: Budget,
+}
+
+impl Parser {
+    pub fn new<R: Read>(buffer: BufReader<R>) -> anyhow::Result<Self> {
+        Self::parse(buffer, &Diagnostics::strict())
+    }
+
+    /// Reads the grid, skipping rows that aren't digits or don't match the first row's width
+    /// in lenient mode.
+    pub fn parse<R: Read>(buffer: BufReader<R>, diagnostics: &Diagnostics) -> anyhow::Result<Self> {
+        let mut rows = 0;
+        let mut columns = 0;
+
+        let mut trees = Vec::new();
+
+        for row in parsing::lines(buffer) {
+            let Some(row_digits) = diagnostics.recover(parse_row(&row?, columns))? else {
+                continue;
+            };
+
+            if columns == 0 {
+                columns = row_digits.len();
+            }
+
+            trees.extend(row_digits);
+
+            rows += 1;
+        }
+
+        Ok(Self {
+            rows,
+            columns,
+            trees,
+            budget: Budget::unlimited(),
+        })
+    }
+
+    /// Stops walking the grid once the budget is spent.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn evaluate(&self) -> anyhow::Result<(usize, u32)> {
+        let mut visible_trees = 0;
+        let mut max_scenic_score = 0;
+
+        // add the edges
+        visible_trees += self.edges();
+
+        for row in 1..self.rows.saturating_sub(1) {
+            self.budget
+                .check(|| format!("row {} of {}", row + 1, self.rows))?;
+
+            for column in 1..self.columns.saturating_sub(1) {
+                let tree = self
+                    .get_tree_index(row, column)
+                    .context("failed to get tree index")?;
+
+                if tree == &0 {
+                    // there's no scenario in which this can be visible
+                    continue;
+                }
+
+                let (visible, trees_can_view) = self.view(row, column, tree);
+
+                if visible {
+                    visible_trees += 1;
+                }
+
+                let scenic_score = trees_can_view.iter().product::<u32>();
+
+                if scenic_score > max_scenic_score {
+                    max_scenic_score = scenic_score;
+                }
+            }
+        }
+
+        Ok((visible_trees, max_scenic_score))
+    }
+
+    /// The same as `evaluate`, but sweeping every row and column once in each direction while keeping
+    /// a stack of the trees that still block the view, instead of walking outwards from every tree.
+    pub fn evaluate_with_stacks(&self) -> anyhow::Result<(usize, u32)> {
+        let mut visible = vec![false; self.trees.len()];
+        let mut scenic_scores = vec![1; self.trees.len()];
+        let mut stack = Vec::new();
+
+        for row in 0..self.rows {
+            self.budget
+                .check(|| format!("row {} of {}", row + 1, self.rows))?;
+
+            let mut line = (0..self.columns)
+                .map(|column| row * self.columns + column)
+                .collect::<Vec<_>>();
+
+            self.sweep(&line, &mut visible, &mut scenic_scores, &mut stack);
+            line.reverse();
+            self.sweep(&line, &mut visible, &mut scenic_scores, &mut stack);
+        }
+
+        for column in 0..self.columns {
+            self.budget
+                .check(|| format!("column {} of {}", column + 1, self.columns))?;
+
+            let mut line = (0..self.rows)
+                .map(|row| row * self.columns + column)
+                .collect::<Vec<_>>();
+
+            self.sweep(&line, &mut visible, &mut scenic_scores, &mut stack);
+            line.reverse();
+            self.sweep(&line, &mut visible, &mut scenic_scores, &mut stack);
+        }
+
+        Ok((
+            visible.iter().filter(|visible| **visible).count(),
+            scenic_scores.into_iter().max().unwrap_or(0),
+        ))
+    }
+
+    /// Looks back along a line of trees, given as indexes into the grid, from each of its trees.
+    fn sweep(
+        &self,
+        line: &[usize],
+        visible: &mut [bool],
+        scenic_scores: &mut [u32],
+        stack: &mut Vec<usize>,
+    ) {
+        // positions along the line of the trees not yet hidden behind a taller one, tallest at the bottom
+        stack.clear();
+
+        for (position, &index) in line.iter().enumerate() {
+            let height = self.trees[index];
+
+            while stack
+                .last()
+                .is_some_and(|&top| self.trees[line[top]] < height)
+            {
+                stack.pop();
+            }
+
+            match stack.last() {
+                Some(&blocking) => scenic_scores[index] *= (position - blocking) as u32,
+                None => {
+                    visible[index] = true;
+                    scenic_scores[index] *= position as u32;
+                }
+            }
+
+            stack.push(position);
+        }
+    }
+
+    /// The tree with the highest scenic score as `(row, column, viewing distances)`,
+    /// where the distances are ordered top, bottom, left and right.
+    pub fn best_scenic_tree(&self) -> anyhow::Result<Option<(usize, usize, [u32; 4])>> {
+        let mut best: Option<(usize, usize, [u32; 4])> = None;
+
+        for row in 1..self.rows.saturating_sub(1) {
+            self.budget
+                .check(|| format!("row {} of {}", row + 1, self.rows))?;
+
+            for column in 1..self.columns.saturating_sub(1) {
+                let tree = self
+                    .get_tree_index(row, column)
+                    .context("failed to get tree index")?;
+
+                let (_, distances) = self.view(row, column, tree);
+
+                if best.is_none_or(|(_, _, best)| {
+                    distances.iter().product::<u32>() > best.iter().product::<u32>()
+                }) {
+                    best = Some((row, column, distances));
+                }
+            }
+        }
+
+        Ok(best)
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+
+    /// How many trees are on the edges of the grid, all of them visible.
+    pub fn edges(&self) -> usize {
+        match (self.rows, self.columns) {
+            (0, _) | (_, 0) => 0,
+            (1, columns) => columns,
+            (rows, 1) => rows,
+            (rows, columns) => rows * 2 + (columns - 2) * 2,
+        }
+    }
+
+    /// Whether the tree is visible from outside the grid and how far it can see
+    /// towards the top, bottom, left and right.
+    fn view(&self, row: usize, column: usize, tree: &u32) -> (bool, [u32; 4]) {
+        // we get the trees in each direction
+        let top_trees = (0..row)
+            .rev()
+            .map(|r| self.get_tree_index(r, column).unwrap_or(&u32::MAX));
+        let bottom_trees =
+            (row + 1..self.rows).map(|r| self.get_tree_index(r, column).unwrap_or(&u32::MAX));
+        let left_trees = (0..column)
+            .rev()
+            .map(|c| self.get_tree_index(row, c).unwrap_or(&u32::MAX));
+        let right_trees =
+            (column + 1..self.columns).map(|c| self.get_tree_index(row, c).unwrap_or(&u32::MAX));
+
+        let fold_func = |(total_trees, trees_can_view, blocked), t| {
+            if blocked {
+                (total_trees + 1, trees_can_view, blocked)
+            } else {
+                (total_trees + 1, trees_can_view + 1, t >= tree)
+            }
+        };
+
+        // we fold the trees in each direction to get the number of trees that can be seen and the blocked status
+        let (_, top_trees_can_view, top_blocked) = top_trees.fold((0u32, 0u32, false), fold_func);
+        let (_, bottom_trees_can_view, bottom_blocked) =
+            bottom_trees.fold((0u32, 0u32, false), fold_func);
+        let (_, left_trees_can_view, left_blocked) =
+            left_trees.fold((0u32, 0u32, false), fold_func);
+        let (_, right_trees_can_view, right_blocked) =
+            right_trees.fold((0u32, 0u32, false), fold_func);
+
+        (
+            !top_blocked || !bottom_blocked || !left_blocked || !right_blocked,
+            [
+                top_trees_can_view,
+                bottom_trees_can_view,
+                left_trees_can_view,
+                right_trees_can_view,
+            ],
+        )
+    }
+
+    fn get_tree_index(&self, row: usize, column: usize) -> Option<&u32> {
+        self.trees.get(row * self.columns + column)
+    }
+}
+
+/// The first row decides how many columns every other row must have, `columns` is 0 until then.
+/// A grid has at least one column, so a blank first row is an error.
+fn parse_row(row: &Line, columns: usize) -> ParseResult<Vec<u32>> {
+    let mut cursor = row.cursor();
+
+    let row_digits = match columns {
+        0 => {
+            let mut row_digits = vec![cursor.digit()?];
+            while !cursor.is_empty() {
+                row_digits.push(cursor.digit()?);
+            }
+            row_digits
+        }
+        _ => (0..columns)
+            .map(|_| cursor.digit())
+            .collect::<ParseResult<Vec<_>>>()?,
+    };
+    cursor.end()?;
+
+    Ok(row_digits)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use common::{Answers, Context};
+
+    use super::*;
+
+    const INPUT: &str = r#"30373
+25512
+65332
+33549
+35390
+"#;
+
+    #[test]
+    fn test_parser() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let parser = Parser::new(reader).unwrap();
+
+        assert_eq!(parser.rows, 5);
+        assert_eq!(parser.columns, 5);
+
+        assert_eq!(parser.trees.len(), parser.rows * parser.columns);
+
+        // thank you copilot for generating these
+        assert_eq!(parser.get_tree_index(0, 0).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(0, 1).unwrap(), &0);
+        assert_eq!(parser.get_tree_index(0, 2).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(0, 3).unwrap(), &7);
+        assert_eq!(parser.get_tree_index(0, 4).unwrap(), &3);
+
+        assert_eq!(parser.get_tree_index(1, 0).unwrap(), &2);
+        assert_eq!(parser.get_tree_index(1, 1).unwrap(), &5);
+        assert_eq!(parser.get_tree_index(1, 2).unwrap(), &5);
+        assert_eq!(parser.get_tree_index(1, 3).unwrap(), &1);
+        assert_eq!(parser.get_tree_index(1, 4).unwrap(), &2);
+
+        assert_eq!(parser.get_tree_index(2, 0).unwrap(), &6);
+        assert_eq!(parser.get_tree_index(2, 1).unwrap(), &5);
+        assert_eq!(parser.get_tree_index(2, 2).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(2, 3).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(2, 4).unwrap(), &2);
+
+        assert_eq!(parser.get_tree_index(3, 0).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(3, 1).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(3, 2).unwrap(), &5);
+        assert_eq!(parser.get_tree_index(3, 3).unwrap(), &4);
+        assert_eq!(parser.get_tree_index(3, 4).unwrap(), &9);
+
+        assert_eq!(parser.get_tree_index(4, 0).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(4, 1).unwrap(), &5);
+        assert_eq!(parser.get_tree_index(4, 2).unwrap(), &3);
+        assert_eq!(parser.get_tree_index(4, 3).unwrap(), &9);
+        assert_eq!(parser.get_tree_index(4, 4).unwrap(), &0);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let parser = Parser::new(reader).unwrap();
+
+        assert_eq!(parser.evaluate().unwrap(), (21, 8));
+        assert_eq!(parser.evaluate_with_stacks().unwrap(), (21, 8));
+        assert_eq!(
+            parser.best_scenic_tree().unwrap(),
+            Some((3, 2, [2, 1, 2, 2]))
+        );
+    }
+
+    #[test]
+    fn test_small_grids() {
+        for (grid, expected) in [
+            ("", (0, 0)),
+            ("5\n", (1, 0)),
+            ("30373\n", (5, 0)),
+            ("3\n0\n3\n", (3, 0)),
+            ("30\n25\n", (4, 0)),
+        ] {
+            let parser = Parser::new(BufReader::new(grid.as_bytes())).unwrap();
+
+            assert_eq!(parser.evaluate().unwrap(), expected, "{grid:?}");
+            assert_eq!(parser.evaluate_with_stacks().unwrap(), expected, "{grid:?}");
+            assert_eq!(parser.edges(), expected.0, "{grid:?}");
+            assert_eq!(parser.best_scenic_tree().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_blank_first_row() {
+        let input = format!("\n{INPUT}");
+
+        for implementation in crate::IMPLEMENTATIONS {
+            let error = (implementation.solve)(&Context::default(), &input).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "line 1, column 1: expected a digit, found end of line"
+            );
+
+            let context = Context {
+                diagnostics: Diagnostics::lenient(),
+                ..Context::default()
+            };
+            assert_eq!(
+                (implementation.solve)(&context, &input).unwrap(),
+                Answers::new(21, 8),
+                "{}",
+                implementation.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let reader = BufReader::new(INPUT.as_bytes());
+        let budget = Budget::unlimited();
+        let parser = Parser::new(reader).unwrap().with_budget(budget.clone());
+
+        budget.cancel();
+
+        let error = parser.evaluate().unwrap_err();
+        assert!(error.to_string().ends_with("stopped at row 2 of 5"));
+    }
+
+    #[test]
+    fn test_parser_error_position() {
+        let input = INPUT.replace("65332", "6533");
+        let reader = BufReader::new(input.as_bytes());
+        let error = Parser::new(reader).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 5: expected a digit, found end of line"
+        );
+
+        let input = INPUT.replace("33549", "335490");
+        let reader = BufReader::new(input.as_bytes());
+        let error = Parser::new(reader).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"line 4, column 6: expected end of line, found "0""#
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = INPUT.replace("65332", "6533\n653x2");
+        let reader = BufReader::new(input.as_bytes());
+
+        let diagnostics = Diagnostics::lenient();
+        let parser = Parser::parse(reader, &diagnostics).unwrap();
+
+        assert_eq!(parser.dimensions(), (4, 5));
+        assert_eq!(
+            diagnostics
+                .warnings()
+                .iter()
+                .map(|warning| warning.line())
+                .collect::<Vec<_>>(),
+            vec![Some(3), Some(4)]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+pub mod parser;
+
+use common::{Answers, Context};
+
+use crate::parser::{Motion, Parser};
+
+/// A rope has at least a head and a tail.
+pub const MIN_KNOTS: usize = 2;
+
+pub fn solve(context: &Context, input: &str) -> anyhow::Result<Answers> {
+    let mut parser_2 = Parser::new(context.config.get_at_least(1, "knots", 2, MIN_KNOTS)?)
+        .with_budget(context.budget.clone());
+    let mut parser_10 = Parser::new(context.config.get_at_least(2, "knots", 10, MIN_KNOTS)?)
+        .with_budget(context.budget.clone());
+
+    let mut trace = context
+        .explain
+        .list(|| "tail positions after each motion".to_string());
+
+    for line in input.lines() {
+        let moved_2 = context.diagnostics.recover(parser_2.move_head(line))?;
+        let moved_10 = context.diagnostics.recover(parser_10.move_head(line))?;
+
+        if moved_2.is_none() || moved_10.is_none() {
+            continue;
+        }
+
+        trace.entry(|| {
+            format!(
+                "{line}: {}; {}",
+                describe_rope(&parser_2),
+                describe_rope(&parser_10)
+            )
+        });
+    }
+
+    drop(trace);
+
+    Ok(Answers::new(
+        parser_2.get_tail_visited_positions(),
+        parser_10.get_tail_visited_positions(),
+    ))
+}
+
+/// Runs the parsers over the input without computing the answers.
+/// The motions are only read, not applied, so huge distances don't matter.
+pub fn validate(context: &Context, input: &str) -> anyhow::Result<()> {
+    for line in parsing::lines(input.as_bytes()) {
+        let line = line?;
+        context.budget.check(|| format!("line {}", line.number))?;
+        context.diagnostics.recover(line.parse::<Motion>())?;
+    }
+
+    Ok(())
+}
+
+fn describe_rope(parser: &Parser) -> String {
+    match parser.get_ends() {
+        Some((head, tail)) => format!(
+            "head at {head:?}, tail at {tail:?} has visited {}",
+            parser.get_tail_visited_positions()
+        ),
+        None => "empty rope".to_string(),
+    }
+}
@@ -0,0 +1,6 @@
+common::track_allocations!();
+
+// https://adventofcode.com/2022/day/9
+fn main() -> anyhow::Result<()> {
+    common::run(2022, 9, y2022_day09::solve)
+}
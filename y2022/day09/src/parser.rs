@@ -1,16 +1,67 @@
 use std::collections::HashSet;
 
 use anyhow::Context;
+use common::Budget;
+use parsing::{Cursor, Parse, ParseResult};
 
-#[derive(Hash, Eq, PartialEq, Clone)]
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn step(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl Parse for Direction {
+    fn parse(cursor: &mut Cursor<'_>) -> ParseResult<Self> {
+        cursor.word_map("a direction (U, D, L or R)", |value| match value {
+            "U" => Some(Direction::Up),
+            "D" => Some(Direction::Down),
+            "L" => Some(Direction::Left),
+            "R" => Some(Direction::Right),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Parse)]
+#[aoc(format = "{} {}")]
+pub struct Motion {
+    direction: Direction,
+    distance: u32,
+}
+
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 struct Segment {
     x: i32,
     y: i32,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Parser {
+    #[cfg_attr(feature = "serde", serde(rename = "knots"))]
     segments: Vec<Segment>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "visited", serialize_with = "serialize_sorted")
+    )]
     tail_visited_positions: HashSet<Segment>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    line: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    budget: Budget,
 }
 
 impl Segment {
@@ -30,23 +81,36 @@ impl Parser {
         Parser {
             segments,
             tail_visited_positions: HashSet::from([Segment::new()]),
+            line: 0,
+            budget: Budget::unlimited(),
         }
     }
 
+    /// Stops moving the rope once the budget is spent.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
     pub fn move_head(&mut self, direction: &str) -> anyhow::Result<()> {
-        let mut parts = direction.split(' ');
-        let direction = parts.next().context("missing direction")?;
-        let distance = parts.next().context("missing distance")?.parse::<i32>()?;
-
-        let (move_x, move_y) = match direction {
-            "U" => (0, -1),
-            "D" => (0, 1),
-            "L" => (-1, 0),
-            "R" => (1, 0),
-            _ => anyhow::bail!("invalid direction"),
-        };
-
-        for _ in 0..distance {
+        self.line += 1;
+
+        let mut cursor = Cursor::at_line(direction, self.line);
+        let motion = cursor.parse::<Motion>()?;
+        cursor.end()?;
+
+        let (move_x, move_y) = motion.direction.step();
+
+        for step in 0..motion.distance {
+            self.budget.check(|| {
+                format!(
+                    "line {}, step {step} of {}, tail visited {} positions",
+                    self.line,
+                    motion.distance,
+                    self.tail_visited_positions.len()
+                )
+            })?;
+
             let head = self
                 .segments
                 .get_mut(0)
@@ -67,6 +131,14 @@ impl Parser {
         self.tail_visited_positions.len()
     }
 
+    /// The `(x, y)` positions of the head and the tail.
+    pub fn get_ends(&self) -> Option<((i32, i32), (i32, i32))> {
+        let head = self.segments.first()?;
+        let tail = self.segments.last()?;
+
+        Some(((head.x, head.y), (tail.x, tail.y)))
+    }
+
     fn move_segment(&mut self, index: usize) -> anyhow::Result<()> {
         let tail_index = self.segments.len() - 1;
 
@@ -132,6 +204,18 @@ impl Parser {
     }
 }
 
+/// The visited positions in a set order, so that dumps of the same input are the same.
+#[cfg(feature = "serde")]
+fn serialize_sorted<S: serde::Serializer>(
+    positions: &HashSet<Segment>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut positions = positions.iter().collect::<Vec<_>>();
+    positions.sort();
+
+    serializer.collect_seq(positions)
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::io::{BufRead, BufReader};
@@ -159,6 +243,7 @@ R 2
         }
 
         assert_eq!(parser.get_tail_visited_positions(), 13);
+        assert_eq!(parser.get_ends(), Some(((2, -2), (1, -2))));
     }
 
     #[test]
@@ -173,4 +258,36 @@ R 2
 
         assert_eq!(parser.get_tail_visited_positions(), 1);
     }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let budget = Budget::unlimited();
+        let mut parser = Parser::new(2).with_budget(budget.clone());
+
+        parser.move_head("R 4").unwrap();
+        budget.cancel();
+
+        let error = parser.move_head("U 4").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("stopped at line 2, step 0 of 4, tail visited 4 positions"));
+    }
+
+    #[test]
+    fn test_parser_error_position() {
+        let mut parser = Parser::new(2);
+        parser.move_head("R 4").unwrap();
+
+        let error = parser.move_head("X 4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 1: expected a direction (U, D, L or R), found "X 4""#
+        );
+
+        let error = parser.move_head("U -4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 3, column 3: expected an integer in range, found "-4""#
+        );
+    }
 }